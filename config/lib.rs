@@ -7,13 +7,17 @@ mod config {
     const SLOPE1: u64 = 4_000_000_000; // +4% pre-optimal
     const SLOPE2: u64 = 75_000_000_000; // +75% post-optimal
     const EXPOSURE_CAP: u64 = 50_000_000; // 5% scaled by 1e9
-    const RESERVE_FACTOR: u8 = 20; // 20%
+    const RESERVE_FACTOR: u8 = 20; // 20%, default low reserve factor
     const MAX_RATE: u64 = 100_000_000_000; // Cap at 100%
+    // Scaling metadata shared by all contracts
+    const RATE_SCALE: u64 = 1_000_000_000; // 1e9: rates are percent * RATE_SCALE, utilization percentages too (100% = 100 * RATE_SCALE)
+    const AMOUNT_SCALE: u128 = 1_000_000_000; // 1e9: one whole token in loan amount units (TOKEN_DECIMALS)
     
     /// All information stored for the configurable parameters of the protocol
     #[ink(storage)]
     pub struct Config {
         admin: AccountId,
+        pending_admin: Option<AccountId>, // Proposed admin awaiting accept_admin
         base_interest_rate: u64,
         boost: u64,
         min_stars_to_vouch: u32,
@@ -29,11 +33,89 @@ mod config {
         loan_tier2_min_vouches: u32,
         loan_tier3_min_stars: u32,
         loan_tier3_min_vouches: u32,
+        // Ceiling on a single loan per tier, in the same scaled units as the tier thresholds
+        loan_tier1_max_amount: Balance,
+        loan_tier2_max_amount: Balance,
+        loan_tier3_max_amount: Balance,
         // Default grace period - time after due date before loan can be marked as defaulted
         default_grace_period: Timestamp,
         // Star-based discount configuration
         star_discount_percent_per_star: u64, // Discount percentage per star (e.g., 1 = 1% per star)
         max_star_discount_percent: u64, // Maximum discount cap (e.g., 50 = 50% max discount)
+        max_vouchers_per_borrower: u32, // Maximum active vouchers backing a single borrower
+        max_vouchers_per_loan: u32, // Maximum vouchers a single loan can consume
+        // Star grant limits
+        max_stars_per_grant: u32, // Maximum stars a single add_stars call can grant
+        max_total_stars: u32, // Ceiling on a user's total stars (0 = no ceiling)
+        // Loan extension configuration
+        max_loan_extensions: u32, // Maximum number of times a single loan can be extended
+        extension_fee_percent: u64, // Fee per extension as a percentage of principal (e.g., 1 = 1%)
+        max_history_len: u32, // Maximum loan/vouch history entries kept per user
+        // Repayment reward curve: base + amount / amount_divisor + term / term_divisor, capped
+        repay_reward_base: u32, // Stars granted for any repaid loan
+        repay_reward_amount_divisor: Balance, // Loan amount per extra star (0 = ignore amount)
+        repay_reward_term_divisor: Timestamp, // Loan term per extra star (0 = ignore term)
+        repay_reward_cap: u32, // Maximum stars granted for a single repaid loan
+        rate_step: u64, // Maximum base rate change per pool rebalance (scaled by 1e9 per percent)
+        // Inactivity: borrowers idle for longer than the window need more stars to borrow
+        inactivity_window: Timestamp, // 0 = no inactivity surcharge
+        inactivity_star_surcharge_percent: u32, // Extra stars required, as a percentage of the tier minimum
+        // Probation after an unban: add_stars accrues only a percentage of the granted stars
+        rehabilitation_cooldown: Timestamp,
+        rehabilitation_accrual_percent: u32, // e.g., 50 = half the stars during rehabilitation
+        loan_cooldown: Timestamp, // Minimum time between loan requests by the same borrower
+        min_capital_per_star: Balance, // Minimum staked capital (10 decimals) per staked star in a vouch (0 = not enforced)
+        // Diminishing vouch boost: full boost for the first N successful vouches, reduced afterwards
+        full_boost_vouches: u32,
+        reduced_boost_percent: u32, // Percentage of the boost granted after the first N (e.g., 50 = half)
+        // Boost penalty for vouchers who recently backed a defaulter
+        default_penalty_window: Timestamp,
+        default_penalty_percent: u32, // Boost reduction per failed vouch inside the window
+        min_vouch_age: Timestamp, // Minimum age of a vouch before it counts toward a loan's requirement
+        liquidation_bounty: Balance, // Reward from the keeper pool for the keeper processing a default (10 decimals)
+        initial_stars: u32, // Stars a new user starts with
+        default_cooldown: Timestamp, // Time after a default before the borrower may request another loan
+        max_vouch_percent: u8, // Maximum share of a voucher's deposit staked in a single vouch
+        interest_free_period: Timestamp, // Window after disbursement in which a full repayment owes principal only
+        // Utilization-tiered reserve factor: the high factor applies above the threshold
+        reserve_factor: u8, // Low factor, at or below the threshold
+        reserve_factor_high: u8,
+        reserve_utilization_threshold: u64, // Percentage scaled by 1e9, like optimal_utilization
+        global_borrow_cap: Balance, // Ceiling on the pool's total borrowed (10 decimals, 0 = unlimited)
+        // Term premium: origination rate rises by term_premium_per_period for each full period of term
+        term_premium_per_period: u64, // Scaled by 1e9 like other rates (0 = no premium)
+        term_premium_period: Timestamp,
+        // Credit tightening under stress: above the utilization threshold, star requirements are multiplied
+        stress_utilization: u64, // Percentage scaled by 1e9, like optimal_utilization
+        stress_multiplier_percent: u32, // e.g., 150 = 1.5x the tier star requirement
+        appeal_window: Timestamp, // Time between marking a loan overdue and finalizing its default
+        keeper_fee_percent: u8, // Share of accrued interest set aside in the pool's keeper-reward pool
+        max_vouches_per_voucher: u32, // Maximum active borrowers a single voucher can back
+        // Effective reputation: own stars plus a share of the stars staked by active vouchers
+        vouch_star_weight_percent: u32,
+        use_effective_reputation: bool, // Tier star checks use the effective reputation instead of own stars
+        // Governance rate limit: at most max_changes_per_window admin changes per change_window
+        max_changes_per_window: u32, // 0 = unlimited
+        change_window: Timestamp,
+        change_window_start: Timestamp, // Start of the current window
+        changes_in_window: u32, // Admin changes made in the current window
+        pending_rate_limit: Option<(u32, Timestamp)>, // Looser limit that takes effect when the current window ends
+        min_liquidity_buffer: Balance, // Liquidity that can never be borrowed, kept for withdrawals (10 decimals)
+        slash_delay: Timestamp, // Delay before a scheduled voucher slash can be committed (0 = slash immediately)
+        // Recency weighting: stars earned from vouch boosts fade over time, base stars don't
+        boost_star_decay_period: Timestamp, // Length of one decay step (0 = boost stars never decay)
+        boost_star_decay_percent: u32, // Share of remaining boost stars lost per period
+        max_installments: u32, // Most installments a single loan may be split into
+        min_accrual_utilization: u64, // No interest accrues below this utilization; percentage scaled by 1e9
+        exposure_cap_absolute: Balance, // Fixed per-borrower exposure cap in 10 decimals (0 = use the liquidity-relative cap)
+        // Harsher default slashing for loans that ran longer before defaulting
+        term_penalty_factor: u32, // Extra percent of the base star slash per full term_penalty_period (0 = amount only)
+        term_penalty_period: Timestamp,
+        smoothing_factor: u32, // Percent weight of the previous rate in the smoothed pool rate (0 = no smoothing)
+        withdraw_cooldown: Timestamp, // Lock on withdrawals after a user's last deposit (0 = none)
+        reserve_lending_percent: u8, // Share of reserves counted as lending capital, earning interest into reserves (0 = idle)
+        loan_max_rate: u64, // Highest rate a loan may lock in at origination, scaled by 1e9 (0 = no ceiling)
+        require_registration: bool, // If true, Reputation never creates users implicitly; they must register first
     }
 
     // Custom error types for the contract
@@ -42,7 +124,9 @@ mod config {
     pub enum Error {
         NotAdmin,
         InvalidValue,
-        AlreadyAdmin
+        AlreadyAdmin,
+        NotPendingAdmin,
+        ChangeRateLimited,
      }
 
     // Custom result type for the contract
@@ -54,6 +138,7 @@ mod config {
         pub fn new(admin_account_id: AccountId) -> Self {
             Self {
                 admin: admin_account_id,
+                pending_admin: None,
                 base_interest_rate: 10_000_000_000, // 10% scaled by 1e9
                 boost: 2_000_000_000, // +2 boost
                 min_stars_to_vouch: 50,
@@ -68,23 +153,141 @@ mod config {
                 loan_tier2_min_vouches: 2,
                 loan_tier3_min_stars: 50,
                 loan_tier3_min_vouches: 3,
+                loan_tier1_max_amount: 1000,
+                loan_tier2_max_amount: 10000,
+                loan_tier3_max_amount: 100000,
                 default_grace_period: 604_800_000, // 7 days in ms
                 star_discount_percent_per_star: 1, // 1% discount per star
                 max_star_discount_percent: 50, // 50% maximum discount cap
+                max_vouchers_per_borrower: 10,
+                max_vouchers_per_loan: 5,
+                max_stars_per_grant: 10,
+                max_total_stars: 10_000,
+                max_loan_extensions: 2,
+                extension_fee_percent: 1, // 1% of principal per extension
+                max_history_len: 50,
+                repay_reward_base: 1,
+                repay_reward_amount_divisor: 1_000_000_000_000, // +1 star per 1000 tokens (9 decimals)
+                repay_reward_term_divisor: 2_592_000_000, // +1 star per 30 days
+                repay_reward_cap: 5,
+                rate_step: 500_000_000, // 0.5% per rebalance
+                inactivity_window: 15_552_000_000, // 180 days in ms
+                inactivity_star_surcharge_percent: 50,
+                rehabilitation_cooldown: 2_592_000_000, // 30 days in ms
+                rehabilitation_accrual_percent: 50,
+                loan_cooldown: 86_400_000, // 1 day in ms
+                min_capital_per_star: 0,
+                full_boost_vouches: 5,
+                reduced_boost_percent: 50,
+                default_penalty_window: 7_776_000_000, // 90 days in ms
+                default_penalty_percent: 25,
+                min_vouch_age: 0, // Vouches count immediately (auto-disbursement on vouch)
+                liquidation_bounty: 10_000_000_000, // 1 token (10 decimals)
+                initial_stars: 7,
+                default_cooldown: 7_776_000_000, // 90 days in ms
+                max_vouch_percent: 100, // No diversification requirement until the admin tightens it
+                interest_free_period: 0, // No interest-free window
+                reserve_factor: RESERVE_FACTOR,
+                reserve_factor_high: 30, // 30% above the threshold
+                reserve_utilization_threshold: OPTIMAL_UTILIZATION,
+                global_borrow_cap: 0, // Unlimited
+                term_premium_per_period: 0,
+                term_premium_period: 2_592_000_000, // 30 days in ms
+                stress_utilization: 95_000_000_000, // 95%
+                stress_multiplier_percent: 150,
+                appeal_window: 259_200_000, // 3 days in ms
+                keeper_fee_percent: 5,
+                max_vouches_per_voucher: 10,
+                vouch_star_weight_percent: 25,
+                use_effective_reputation: false,
+                max_changes_per_window: 20,
+                change_window: 86_400_000, // 1 day in ms
+                change_window_start: 0,
+                changes_in_window: 0,
+                pending_rate_limit: None,
+                min_liquidity_buffer: 0,
+                slash_delay: 0,
+                boost_star_decay_period: 0,
+                boost_star_decay_percent: 10,
+                max_installments: 12,
+                min_accrual_utilization: 0,
+                exposure_cap_absolute: 0,
+                term_penalty_factor: 0,
+                term_penalty_period: 2_592_000_000, // 30 days in ms
+                smoothing_factor: 0,
+                withdraw_cooldown: 0,
+                reserve_lending_percent: 0,
+                loan_max_rate: 0,
+                require_registration: false,
             }
         }
 
+        /// Get the protocol admin
+        /// Dependent contracts read this for their own admin gating instead of storing a copy
         #[ink(message)]
         pub fn get_admin(&self) -> AccountId {
             self.admin
         }
 
+        /// Propose a new admin; the transfer completes only when they call accept_admin
+        /// Proposing again replaces the pending admin
+        #[ink(message)]
+        pub fn propose_admin(&mut self, new_admin: AccountId, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if new_admin == self.admin {
+                return Err(Error::AlreadyAdmin);
+            }
+            self.pending_admin = Some(new_admin);
+            Ok(())
+        }
+
+        /// Accept a pending admin transfer; only the proposed admin may call this
+        #[ink(message)]
+        pub fn accept_admin(&mut self, caller_account_id: AccountId) -> ConfigResult<()> {
+            if self.pending_admin != Some(caller_account_id) {
+                return Err(Error::NotPendingAdmin);
+            }
+            self.admin = caller_account_id;
+            self.pending_admin = None;
+            Ok(())
+        }
+
+        /// Get the admin proposed by propose_admin, if a transfer is in progress
+        /// Lets the incoming admin confirm they are the designated recipient before accepting
+        #[ink(message)]
+        pub fn get_pending_admin(&self) -> Option<AccountId> {
+            self.pending_admin
+        }
 
         /// Ensure that the caller of other functions is the admin
+        /// Every admin change also counts toward the governance rate limit
         fn ensure_admin(&mut self, caller_account_id: AccountId) -> ConfigResult<()> {
             if caller_account_id != self.admin {
                 return Err(Error::NotAdmin);
             }
+            self.record_change()
+        }
+
+        /// Count an admin change in the current window, rejecting it once the window is full
+        /// A rejected setter reverts, so only applied changes are counted
+        /// A scheduled looser limit is applied when a new window starts
+        fn record_change(&mut self) -> ConfigResult<()> {
+            let now = self.env().block_timestamp();
+            if now.saturating_sub(self.change_window_start) >= self.change_window {
+                if let Some((max_changes, window)) = self.pending_rate_limit.take() {
+                    self.max_changes_per_window = max_changes;
+                    self.change_window = window;
+                }
+                self.change_window_start = now;
+                self.changes_in_window = 0;
+            }
+            if self.max_changes_per_window == 0 {
+                return Ok(());
+            }
+            if self.changes_in_window >= self.max_changes_per_window {
+                return Err(Error::ChangeRateLimited);
+            }
+            self.changes_in_window += 1;
             Ok(())
         }
 
@@ -119,16 +322,23 @@ mod config {
             EXPOSURE_CAP
         }
         
-        #[ink(message)]
-        pub fn get_reserve_factor(&self) -> u8 {
-            RESERVE_FACTOR
-        }
-        
         #[ink(message)]
         pub fn get_max_rate(&self) -> u64 {
             MAX_RATE
         }
 
+        /// Scale of rates and utilization, both percentages: 1% is RATE_SCALE (100% utilization = 100 * RATE_SCALE)
+        #[ink(message)]
+        pub fn get_rate_scale(&self) -> u64 {
+            RATE_SCALE
+        }
+
+        /// Scale of loan amounts: one whole token
+        #[ink(message)]
+        pub fn get_amount_scale(&self) -> Balance {
+            AMOUNT_SCALE
+        }
+
 
         #[ink(message)]
         pub fn update_boost(&mut self, new_boost: u64, caller_account_id: AccountId) -> ConfigResult<()> {
@@ -204,6 +414,27 @@ mod config {
             Ok(())
         }
 
+        #[ink(message)]
+        pub fn update_loan_tier1_max_amount(&mut self, new_max: Balance, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.loan_tier1_max_amount = new_max;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_loan_tier2_max_amount(&mut self, new_max: Balance, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.loan_tier2_max_amount = new_max;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_loan_tier3_max_amount(&mut self, new_max: Balance, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.loan_tier3_max_amount = new_max;
+            Ok(())
+        }
+
         #[ink(message)]
         pub fn update_default_grace_period(&mut self, new_period: Timestamp, caller_account_id: AccountId) -> ConfigResult<()> {
             self.ensure_admin(caller_account_id)?;
@@ -229,6 +460,383 @@ mod config {
             Ok(())
         }
 
+        #[ink(message)]
+        pub fn update_max_vouchers_per_borrower(&mut self, new_max: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.max_vouchers_per_borrower = new_max;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_max_stars_per_grant(&mut self, new_max: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.max_stars_per_grant = new_max;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_max_total_stars(&mut self, new_max: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.max_total_stars = new_max;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_max_loan_extensions(&mut self, new_max: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.max_loan_extensions = new_max;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_extension_fee_percent(&mut self, new_fee: u64, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if new_fee > 100 {
+                return Err(Error::InvalidValue);
+            }
+            self.extension_fee_percent = new_fee;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_max_history_len(&mut self, new_len: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.max_history_len = new_len;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_repay_reward_curve(&mut self, base: u32, amount_divisor: Balance, term_divisor: Timestamp, cap: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if base > cap {
+                return Err(Error::InvalidValue);
+            }
+            self.repay_reward_base = base;
+            self.repay_reward_amount_divisor = amount_divisor;
+            self.repay_reward_term_divisor = term_divisor;
+            self.repay_reward_cap = cap;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_max_vouchers_per_loan(&mut self, new_max: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            // A loan needs at least one voucher to ever be disbursed
+            if new_max == 0 {
+                return Err(Error::InvalidValue);
+            }
+            self.max_vouchers_per_loan = new_max;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_rate_step(&mut self, new_step: u64, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.rate_step = new_step;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_inactivity_surcharge(&mut self, window: Timestamp, surcharge_percent: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.inactivity_window = window;
+            self.inactivity_star_surcharge_percent = surcharge_percent;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_rehabilitation_cooldown(&mut self, cooldown: Timestamp, accrual_percent: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if accrual_percent > 100 {
+                return Err(Error::InvalidValue);
+            }
+            self.rehabilitation_cooldown = cooldown;
+            self.rehabilitation_accrual_percent = accrual_percent;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_loan_cooldown(&mut self, new_cooldown: Timestamp, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.loan_cooldown = new_cooldown;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_min_capital_per_star(&mut self, new_min: Balance, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.min_capital_per_star = new_min;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_boost_decay(&mut self, full_boost_vouches: u32, reduced_boost_percent: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if reduced_boost_percent > 100 {
+                return Err(Error::InvalidValue);
+            }
+            self.full_boost_vouches = full_boost_vouches;
+            self.reduced_boost_percent = reduced_boost_percent;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_default_penalty(&mut self, window: Timestamp, penalty_percent: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if penalty_percent > 100 {
+                return Err(Error::InvalidValue);
+            }
+            self.default_penalty_window = window;
+            self.default_penalty_percent = penalty_percent;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_min_vouch_age(&mut self, new_age: Timestamp, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.min_vouch_age = new_age;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_liquidation_bounty(&mut self, new_bounty: Balance, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.liquidation_bounty = new_bounty;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_initial_stars(&mut self, new_initial_stars: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.initial_stars = new_initial_stars;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_default_cooldown(&mut self, new_cooldown: Timestamp, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.default_cooldown = new_cooldown;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_max_vouch_percent(&mut self, new_percent: u8, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if new_percent == 0 || new_percent > 100 {
+                return Err(Error::InvalidValue);
+            }
+            self.max_vouch_percent = new_percent;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_interest_free_period(&mut self, new_period: Timestamp, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.interest_free_period = new_period;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_reserve_factor_tiers(&mut self, low_factor: u8, high_factor: u8, utilization_threshold: u64, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if high_factor > 100 || high_factor < low_factor {
+                return Err(Error::InvalidValue);
+            }
+            self.reserve_factor = low_factor;
+            self.reserve_factor_high = high_factor;
+            self.reserve_utilization_threshold = utilization_threshold;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_global_borrow_cap(&mut self, new_cap: Balance, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.global_borrow_cap = new_cap;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_term_premium(&mut self, premium_per_period: u64, period: Timestamp, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if period == 0 {
+                return Err(Error::InvalidValue);
+            }
+            self.term_premium_per_period = premium_per_period;
+            self.term_premium_period = period;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_stress_requirements(&mut self, stress_utilization: u64, multiplier_percent: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if multiplier_percent < 100 {
+                return Err(Error::InvalidValue);
+            }
+            self.stress_utilization = stress_utilization;
+            self.stress_multiplier_percent = multiplier_percent;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_appeal_window(&mut self, new_window: Timestamp, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.appeal_window = new_window;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_keeper_fee_percent(&mut self, new_percent: u8, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if new_percent > 100 {
+                return Err(Error::InvalidValue);
+            }
+            self.keeper_fee_percent = new_percent;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_max_vouches_per_voucher(&mut self, new_max: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if new_max == 0 {
+                return Err(Error::InvalidValue);
+            }
+            self.max_vouches_per_voucher = new_max;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_effective_reputation(&mut self, vouch_star_weight_percent: u32, enabled: bool, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if vouch_star_weight_percent > 100 {
+                return Err(Error::InvalidValue);
+            }
+            self.vouch_star_weight_percent = vouch_star_weight_percent;
+            self.use_effective_reputation = enabled;
+            Ok(())
+        }
+
+        /// Tightening applies immediately; loosening (more changes, a shorter window, or
+        /// 0 = unlimited) only takes effect once the current window ends, so the limit
+        /// can't be lifted to make further changes inside the window it is enforcing
+        #[ink(message)]
+        pub fn update_change_rate_limit(&mut self, max_changes_per_window: u32, change_window: Timestamp, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            let loosens = self.max_changes_per_window != 0
+                && (max_changes_per_window == 0
+                    || max_changes_per_window > self.max_changes_per_window
+                    || change_window < self.change_window);
+            if loosens {
+                self.pending_rate_limit = Some((max_changes_per_window, change_window));
+            } else {
+                self.max_changes_per_window = max_changes_per_window;
+                self.change_window = change_window;
+                self.pending_rate_limit = None;
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_min_liquidity_buffer(&mut self, new_buffer: Balance, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.min_liquidity_buffer = new_buffer;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_slash_delay(&mut self, new_delay: Timestamp, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.slash_delay = new_delay;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_boost_star_decay(&mut self, period: Timestamp, percent: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if percent > 100 {
+                return Err(Error::InvalidValue);
+            }
+            self.boost_star_decay_period = period;
+            self.boost_star_decay_percent = percent;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_max_installments(&mut self, new_max: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if new_max == 0 {
+                return Err(Error::InvalidValue);
+            }
+            self.max_installments = new_max;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_min_accrual_utilization(&mut self, new_min: u64, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.min_accrual_utilization = new_min;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_exposure_cap_absolute(&mut self, new_cap: Balance, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.exposure_cap_absolute = new_cap;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_term_penalty(&mut self, factor: u32, period: Timestamp, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if period == 0 {
+                return Err(Error::InvalidValue);
+            }
+            self.term_penalty_factor = factor;
+            self.term_penalty_period = period;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_smoothing_factor(&mut self, new_factor: u32, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            // 100 would freeze the rate forever
+            if new_factor >= 100 {
+                return Err(Error::InvalidValue);
+            }
+            self.smoothing_factor = new_factor;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_withdraw_cooldown(&mut self, new_cooldown: Timestamp, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.withdraw_cooldown = new_cooldown;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_reserve_lending_percent(&mut self, new_percent: u8, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            if new_percent > 100 {
+                return Err(Error::InvalidValue);
+            }
+            self.reserve_lending_percent = new_percent;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_loan_max_rate(&mut self, new_rate: u64, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.loan_max_rate = new_rate;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_require_registration(&mut self, required: bool, caller_account_id: AccountId) -> ConfigResult<()> {
+            self.ensure_admin(caller_account_id)?;
+            self.require_registration = required;
+            Ok(())
+        }
+
         /// Getter functions for configuration parameters
 
         #[ink(message)]
@@ -294,6 +902,24 @@ mod config {
             (self.loan_tier3_min_stars, self.loan_tier3_min_vouches)
         }
 
+        /// Getter for tier 1 maximum loan amount (scaled)
+        #[ink(message)]
+        pub fn loan_tier1_max_amount(&self) -> Balance {
+            self.loan_tier1_max_amount
+        }
+
+        /// Getter for tier 2 maximum loan amount (scaled)
+        #[ink(message)]
+        pub fn loan_tier2_max_amount(&self) -> Balance {
+            self.loan_tier2_max_amount
+        }
+
+        /// Getter for tier 3 maximum loan amount (scaled)
+        #[ink(message)]
+        pub fn loan_tier3_max_amount(&self) -> Balance {
+            self.loan_tier3_max_amount
+        }
+
         /// Getter for default grace period
         #[ink(message)]
         pub fn get_default_grace_period(&self) -> Timestamp {
@@ -311,6 +937,577 @@ mod config {
         pub fn get_max_star_discount_percent(&self) -> u64 {
             self.max_star_discount_percent
         }
+
+        /// Getter for maximum active vouchers per borrower
+        #[ink(message)]
+        pub fn get_max_vouchers_per_borrower(&self) -> u32 {
+            self.max_vouchers_per_borrower
+        }
+
+        /// Getter for maximum stars a single grant can add
+        #[ink(message)]
+        pub fn get_max_stars_per_grant(&self) -> u32 {
+            self.max_stars_per_grant
+        }
+
+        /// Getter for the ceiling on a user's total stars (0 = no ceiling)
+        #[ink(message)]
+        pub fn get_max_total_stars(&self) -> u32 {
+            self.max_total_stars
+        }
+
+        /// Getter for maximum number of extensions per loan
+        #[ink(message)]
+        pub fn get_max_loan_extensions(&self) -> u32 {
+            self.max_loan_extensions
+        }
+
+        /// Getter for extension fee percent of principal
+        #[ink(message)]
+        pub fn get_extension_fee_percent(&self) -> u64 {
+            self.extension_fee_percent
+        }
+
+        /// Getter for maximum history entries kept per user
+        #[ink(message)]
+        pub fn get_max_history_len(&self) -> u32 {
+            self.max_history_len
+        }
+
+        /// Getter for the repayment reward curve (base, amount_divisor, term_divisor, cap)
+        #[ink(message)]
+        pub fn get_repay_reward_curve(&self) -> (u32, Balance, Timestamp, u32) {
+            (
+                self.repay_reward_base,
+                self.repay_reward_amount_divisor,
+                self.repay_reward_term_divisor,
+                self.repay_reward_cap,
+            )
+        }
+
+        /// Getter for maximum vouchers a single loan can consume
+        #[ink(message)]
+        pub fn get_max_vouchers_per_loan(&self) -> u32 {
+            self.max_vouchers_per_loan
+        }
+
+        /// Getter for the maximum base rate change per pool rebalance
+        #[ink(message)]
+        pub fn get_rate_step(&self) -> u64 {
+            self.rate_step
+        }
+
+        /// Getter for the inactivity surcharge (window, extra stars percent)
+        #[ink(message)]
+        pub fn get_inactivity_surcharge(&self) -> (Timestamp, u32) {
+            (self.inactivity_window, self.inactivity_star_surcharge_percent)
+        }
+
+        /// Getter for the rehabilitation cooldown (duration, accrual percent)
+        #[ink(message)]
+        pub fn get_rehabilitation_cooldown(&self) -> (Timestamp, u32) {
+            (self.rehabilitation_cooldown, self.rehabilitation_accrual_percent)
+        }
+
+        /// Getter for minimum time between loan requests by the same borrower
+        #[ink(message)]
+        pub fn get_loan_cooldown(&self) -> Timestamp {
+            self.loan_cooldown
+        }
+
+        /// Getter for minimum staked capital per staked star in a vouch
+        #[ink(message)]
+        pub fn get_min_capital_per_star(&self) -> Balance {
+            self.min_capital_per_star
+        }
+
+        /// Getter for the diminishing vouch boost (full_boost_vouches, reduced_boost_percent)
+        #[ink(message)]
+        pub fn get_boost_decay(&self) -> (u32, u32) {
+            (self.full_boost_vouches, self.reduced_boost_percent)
+        }
+
+        /// Getter for the recent-default boost penalty (window, percent per failed vouch)
+        #[ink(message)]
+        pub fn get_default_penalty(&self) -> (Timestamp, u32) {
+            (self.default_penalty_window, self.default_penalty_percent)
+        }
+
+        /// Getter for the minimum age of a vouch before it counts toward a loan
+        #[ink(message)]
+        pub fn get_min_vouch_age(&self) -> Timestamp {
+            self.min_vouch_age
+        }
+
+        /// Getter for the keeper reward paid when a default is processed (10 decimals)
+        #[ink(message)]
+        pub fn get_liquidation_bounty(&self) -> Balance {
+            self.liquidation_bounty
+        }
+
+        /// Getter for the stars a new user starts with
+        #[ink(message)]
+        pub fn get_initial_stars(&self) -> u32 {
+            self.initial_stars
+        }
+
+        /// Getter for the cooldown after a default before the borrower may borrow again
+        #[ink(message)]
+        pub fn get_default_cooldown(&self) -> Timestamp {
+            self.default_cooldown
+        }
+
+        /// Getter for the maximum share of a deposit staked in a single vouch
+        #[ink(message)]
+        pub fn get_max_vouch_percent(&self) -> u8 {
+            self.max_vouch_percent
+        }
+
+        /// Getter for the interest-free window after loan disbursement
+        #[ink(message)]
+        pub fn get_interest_free_period(&self) -> Timestamp {
+            self.interest_free_period
+        }
+
+        /// Getter for the utilization-tiered reserve factors
+        /// Returns (low factor, high factor, utilization threshold); the low factor is `get_reserve_factor`
+        #[ink(message)]
+        pub fn get_reserve_factor_tiers(&self) -> (u8, u8, u64) {
+            (self.reserve_factor, self.reserve_factor_high, self.reserve_utilization_threshold)
+        }
+
+        /// Getter for the ceiling on total borrowed across the pool (10 decimals, 0 = unlimited)
+        #[ink(message)]
+        pub fn get_global_borrow_cap(&self) -> Balance {
+            self.global_borrow_cap
+        }
+
+        /// Getter for the term premium as (premium per period, period length)
+        #[ink(message)]
+        pub fn get_term_premium(&self) -> (u64, Timestamp) {
+            (self.term_premium_per_period, self.term_premium_period)
+        }
+
+        /// Getter for the stress tightening as (utilization threshold, star multiplier percent)
+        #[ink(message)]
+        pub fn get_stress_requirements(&self) -> (u64, u32) {
+            (self.stress_utilization, self.stress_multiplier_percent)
+        }
+
+        /// Getter for the appeal window between marking a loan overdue and finalizing its default
+        #[ink(message)]
+        pub fn get_appeal_window(&self) -> Timestamp {
+            self.appeal_window
+        }
+
+        /// Getter for the share of accrued interest that funds keeper rewards
+        #[ink(message)]
+        pub fn get_keeper_fee_percent(&self) -> u8 {
+            self.keeper_fee_percent
+        }
+
+        /// Getter for the maximum active borrowers a single voucher can back
+        #[ink(message)]
+        pub fn get_max_vouches_per_voucher(&self) -> u32 {
+            self.max_vouches_per_voucher
+        }
+
+        /// Getter for the effective reputation settings as (vouch star weight percent, used in tier checks)
+        #[ink(message)]
+        pub fn get_effective_reputation(&self) -> (u32, bool) {
+            (self.vouch_star_weight_percent, self.use_effective_reputation)
+        }
+
+        /// Getter for the governance rate limit as (max changes per window, window length, changes in current window)
+        #[ink(message)]
+        pub fn get_change_rate_limit(&self) -> (u32, Timestamp, u32) {
+            (self.max_changes_per_window, self.change_window, self.changes_in_window)
+        }
+
+        /// Getter for a looser rate limit scheduled for the next window, as (max changes per window, window length)
+        #[ink(message)]
+        pub fn get_pending_rate_limit(&self) -> Option<(u32, Timestamp)> {
+            self.pending_rate_limit
+        }
+
+        /// Getter for the liquidity that can never be borrowed (10 decimals)
+        #[ink(message)]
+        pub fn get_min_liquidity_buffer(&self) -> Balance {
+            self.min_liquidity_buffer
+        }
+
+        /// Getter for the delay before a scheduled voucher slash can be committed
+        #[ink(message)]
+        pub fn get_slash_delay(&self) -> Timestamp {
+            self.slash_delay
+        }
+
+        /// Getter for the decay of boost-derived stars (period, percent lost per period)
+        #[ink(message)]
+        pub fn get_boost_star_decay(&self) -> (Timestamp, u32) {
+            (self.boost_star_decay_period, self.boost_star_decay_percent)
+        }
+
+        /// Getter for the maximum number of installments per loan
+        #[ink(message)]
+        pub fn get_max_installments(&self) -> u32 {
+            self.max_installments
+        }
+
+        /// Getter for the utilization below which interest doesn't accrue
+        #[ink(message)]
+        pub fn get_min_accrual_utilization(&self) -> u64 {
+            self.min_accrual_utilization
+        }
+
+        /// Getter for the absolute per-borrower exposure cap (0 = liquidity-relative cap applies)
+        #[ink(message)]
+        pub fn get_exposure_cap_absolute(&self) -> Balance {
+            self.exposure_cap_absolute
+        }
+
+        /// Getter for the default slash term penalty as (extra percent per period, period length)
+        #[ink(message)]
+        pub fn get_term_penalty(&self) -> (u32, Timestamp) {
+            (self.term_penalty_factor, self.term_penalty_period)
+        }
+
+        /// Getter for the interest rate smoothing factor (percent weight of the previous rate)
+        #[ink(message)]
+        pub fn get_smoothing_factor(&self) -> u32 {
+            self.smoothing_factor
+        }
+
+        /// Getter for the withdrawal lock after a deposit
+        #[ink(message)]
+        pub fn get_withdraw_cooldown(&self) -> Timestamp {
+            self.withdraw_cooldown
+        }
+
+        /// Getter for the share of reserves counted as lending capital
+        #[ink(message)]
+        pub fn get_reserve_lending_percent(&self) -> u8 {
+            self.reserve_lending_percent
+        }
+
+        /// Getter for the ceiling on a loan's stored interest rate (0 = none)
+        #[ink(message)]
+        pub fn get_loan_max_rate(&self) -> u64 {
+            self.loan_max_rate
+        }
+
+        /// Getter for whether users must register before earning or staking stars
+        #[ink(message)]
+        pub fn get_require_registration(&self) -> bool {
+            self.require_registration
+        }
+
+        /// Getter for the reserve factor applied at or below the utilization threshold
+        #[ink(message)]
+        pub fn get_reserve_factor(&self) -> u8 {
+            self.reserve_factor
+        }
     }
 
-}
\ No newline at end of file
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const ADMIN: [u8; 32] = [0x01; 32];
+        const USER: [u8; 32] = [0x02; 32];
+
+        fn admin() -> AccountId {
+            AccountId::from(ADMIN)
+        }
+
+        fn user() -> AccountId {
+            AccountId::from(USER)
+        }
+
+        #[ink::test]
+        fn tier_max_amounts_are_admin_settable() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.update_loan_tier1_max_amount(500, admin()), Ok(()));
+            assert_eq!(config.update_loan_tier2_max_amount(5_000, admin()), Ok(()));
+            assert_eq!(config.update_loan_tier3_max_amount(50_000, admin()), Ok(()));
+            assert_eq!(config.loan_tier1_max_amount(), 500);
+            assert_eq!(config.loan_tier2_max_amount(), 5_000);
+            assert_eq!(config.loan_tier3_max_amount(), 50_000);
+        }
+
+        #[ink::test]
+        fn tier_max_amounts_reject_non_admin() {
+            let mut config = Config::new(admin());
+            let before = config.loan_tier1_max_amount();
+            assert_eq!(config.update_loan_tier1_max_amount(1, user()), Err(Error::NotAdmin));
+            assert_eq!(config.loan_tier1_max_amount(), before);
+        }
+
+        #[ink::test]
+        fn max_vouchers_per_loan_rejects_zero() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.update_max_vouchers_per_loan(0, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_max_vouchers_per_loan(3, admin()), Ok(()));
+            assert_eq!(config.get_max_vouchers_per_loan(), 3);
+        }
+
+        #[ink::test]
+        fn extension_fee_rejects_more_than_hundred_percent() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.update_extension_fee_percent(101, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_extension_fee_percent(100, admin()), Ok(()));
+            assert_eq!(config.get_extension_fee_percent(), 100);
+        }
+
+        #[ink::test]
+        fn repay_reward_curve_rejects_base_above_cap() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.update_repay_reward_curve(6, 1_000, 86_400_000, 5, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_repay_reward_curve(1, 1_000, 86_400_000, 5, admin()), Ok(()));
+            assert_eq!(config.get_repay_reward_curve(), (1, 1_000, 86_400_000, 5));
+        }
+
+        #[ink::test]
+        fn rehabilitation_accrual_rejects_more_than_hundred_percent() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.update_rehabilitation_cooldown(1_000, 101, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_rehabilitation_cooldown(1_000, 50, admin()), Ok(()));
+            assert_eq!(config.get_rehabilitation_cooldown(), (1_000, 50));
+        }
+
+        #[ink::test]
+        fn boost_decay_rejects_more_than_hundred_percent() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.update_boost_decay(1, 101, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_boost_decay(1, 25, admin()), Ok(()));
+            assert_eq!(config.get_boost_decay(), (1, 25));
+        }
+
+        #[ink::test]
+        fn default_penalty_rejects_more_than_hundred_percent() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.update_default_penalty(1_000, 101, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_default_penalty(1_000, 40, admin()), Ok(()));
+            assert_eq!(config.get_default_penalty(), (1_000, 40));
+        }
+
+        #[ink::test]
+        fn mul_div_rounds_in_the_requested_direction() {
+            assert_eq!(crate::mul_div(10, 1, 3, crate::Rounding::Down), Some(3));
+            assert_eq!(crate::mul_div(10, 1, 3, crate::Rounding::Up), Some(4));
+            // Exact results are unaffected by the direction
+            assert_eq!(crate::mul_div(9, 1, 3, crate::Rounding::Up), Some(3));
+        }
+
+        #[ink::test]
+        fn mul_div_rejects_zero_denominator_and_overflow() {
+            assert_eq!(crate::mul_div(1, 1, 0, crate::Rounding::Down), None);
+            assert_eq!(crate::mul_div(u128::MAX, 2, 1, crate::Rounding::Down), None);
+        }
+
+        #[ink::test]
+        fn max_vouch_percent_defaults_to_hundred_and_rejects_out_of_range() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.get_max_vouch_percent(), 100);
+            assert_eq!(config.update_max_vouch_percent(0, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_max_vouch_percent(101, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_max_vouch_percent(30, admin()), Ok(()));
+            assert_eq!(config.get_max_vouch_percent(), 30);
+        }
+
+        #[ink::test]
+        fn admin_transfer_needs_proposal_and_acceptance() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.propose_admin(admin(), admin()), Err(Error::AlreadyAdmin));
+            assert_eq!(config.propose_admin(user(), user()), Err(Error::NotAdmin));
+            assert_eq!(config.accept_admin(user()), Err(Error::NotPendingAdmin));
+
+            assert_eq!(config.propose_admin(user(), admin()), Ok(()));
+            assert_eq!(config.get_pending_admin(), Some(user()));
+            // Proposing does not hand over the role yet
+            assert_eq!(config.get_admin(), admin());
+            assert_eq!(config.accept_admin(admin()), Err(Error::NotPendingAdmin));
+
+            assert_eq!(config.accept_admin(user()), Ok(()));
+            assert_eq!(config.get_admin(), user());
+            assert_eq!(config.get_pending_admin(), None);
+            assert_eq!(config.update_boost(1, admin()), Err(Error::NotAdmin));
+        }
+
+        #[ink::test]
+        fn reserve_factor_tiers_are_ordered_and_bounded() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.update_reserve_factor_tiers(40, 30, 80_000_000_000, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_reserve_factor_tiers(10, 101, 80_000_000_000, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_reserve_factor_tiers(10, 30, 80_000_000_000, admin()), Ok(()));
+            assert_eq!(config.get_reserve_factor_tiers(), (10, 30, 80_000_000_000));
+            // The low tier is the reserve factor everything else reads
+            assert_eq!(config.get_reserve_factor(), 10);
+        }
+
+        #[ink::test]
+        fn term_premium_rejects_zero_period() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.update_term_premium(1_000_000_000, 0, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_term_premium(1_000_000_000, 86_400_000, admin()), Ok(()));
+            assert_eq!(config.get_term_premium(), (1_000_000_000, 86_400_000));
+        }
+
+        #[ink::test]
+        fn stress_multiplier_cannot_loosen_requirements() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.update_stress_requirements(90_000_000_000, 99, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_stress_requirements(90_000_000_000, 150, admin()), Ok(()));
+            assert_eq!(config.get_stress_requirements(), (90_000_000_000, 150));
+        }
+
+        #[ink::test]
+        fn keeper_fee_rejects_more_than_hundred_percent() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.update_keeper_fee_percent(101, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_keeper_fee_percent(5, admin()), Ok(()));
+            assert_eq!(config.get_keeper_fee_percent(), 5);
+        }
+
+        #[ink::test]
+        fn max_vouches_per_voucher_rejects_zero() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.update_max_vouches_per_voucher(0, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_max_vouches_per_voucher(2, admin()), Ok(()));
+            assert_eq!(config.get_max_vouches_per_voucher(), 2);
+        }
+
+        #[ink::test]
+        fn effective_reputation_weight_rejects_more_than_hundred_percent() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.update_effective_reputation(101, true, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_effective_reputation(50, true, admin()), Ok(()));
+            assert_eq!(config.get_effective_reputation(), (50, true));
+        }
+
+        const DAY: Timestamp = 86_400_000;
+
+        fn set_time(timestamp: Timestamp) {
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(timestamp);
+        }
+
+        #[ink::test]
+        fn admin_changes_are_rate_limited_per_window() {
+            let mut config = Config::new(admin());
+            set_time(1_000);
+            assert_eq!(config.update_change_rate_limit(2, DAY, admin()), Ok(()));
+            assert_eq!(config.update_boost(1, admin()), Ok(()));
+            assert_eq!(config.update_boost(2, admin()), Err(Error::ChangeRateLimited));
+            assert_eq!(config.get_boost(), 1);
+
+            // A new window starts counting from zero
+            set_time(DAY + 1_000);
+            assert_eq!(config.update_boost(3, admin()), Ok(()));
+            assert_eq!(config.get_change_rate_limit(), (2, DAY, 1));
+        }
+
+        #[ink::test]
+        fn tightening_the_rate_limit_applies_immediately() {
+            let mut config = Config::new(admin());
+            set_time(1_000);
+            assert_eq!(config.update_change_rate_limit(1, DAY, admin()), Ok(()));
+            assert_eq!(config.get_change_rate_limit(), (1, DAY, 1));
+            assert_eq!(config.get_pending_rate_limit(), None);
+            assert_eq!(config.update_boost(1, admin()), Err(Error::ChangeRateLimited));
+        }
+
+        #[ink::test]
+        fn loosening_the_rate_limit_waits_for_the_next_window() {
+            let mut config = Config::new(admin());
+            set_time(1_000);
+            assert_eq!(config.update_change_rate_limit(2, DAY, admin()), Ok(()));
+            // Lifting the limit inside the window it is enforcing is only scheduled
+            assert_eq!(config.update_change_rate_limit(0, DAY, admin()), Ok(()));
+            assert_eq!(config.get_change_rate_limit(), (2, DAY, 2));
+            assert_eq!(config.get_pending_rate_limit(), Some((0, DAY)));
+            assert_eq!(config.update_boost(1, admin()), Err(Error::ChangeRateLimited));
+
+            // The looser limit applies once the window rolls over
+            set_time(DAY + 1_000);
+            assert_eq!(config.update_boost(2, admin()), Ok(()));
+            assert_eq!(config.update_boost(3, admin()), Ok(()));
+            assert_eq!(config.update_boost(4, admin()), Ok(()));
+            assert_eq!(config.get_pending_rate_limit(), None);
+            assert_eq!(config.get_change_rate_limit().0, 0);
+        }
+
+        #[ink::test]
+        fn shorter_window_counts_as_loosening() {
+            let mut config = Config::new(admin());
+            set_time(1_000);
+            assert_eq!(config.update_change_rate_limit(20, 1_000, admin()), Ok(()));
+            assert_eq!(config.get_change_rate_limit().1, DAY);
+            assert_eq!(config.get_pending_rate_limit(), Some((20, 1_000)));
+        }
+
+        #[ink::test]
+        fn max_installments_rejects_zero() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.update_max_installments(0, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_max_installments(6, admin()), Ok(()));
+            assert_eq!(config.get_max_installments(), 6);
+        }
+
+        #[ink::test]
+        fn term_penalty_rejects_zero_period() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.update_term_penalty(10, 0, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_term_penalty(10, 1_000, admin()), Ok(()));
+            assert_eq!(config.get_term_penalty(), (10, 1_000));
+        }
+
+        #[ink::test]
+        fn smoothing_factor_cannot_freeze_the_rate() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.update_smoothing_factor(100, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_smoothing_factor(99, admin()), Ok(()));
+            assert_eq!(config.get_smoothing_factor(), 99);
+        }
+
+        #[ink::test]
+        fn reserve_lending_percent_rejects_more_than_hundred_percent() {
+            let mut config = Config::new(admin());
+            assert_eq!(config.get_reserve_lending_percent(), 0);
+            assert_eq!(config.update_reserve_lending_percent(101, admin()), Err(Error::InvalidValue));
+            assert_eq!(config.update_reserve_lending_percent(50, admin()), Ok(()));
+            assert_eq!(config.get_reserve_lending_percent(), 50);
+        }
+
+        #[ink::test]
+        fn registration_is_optional_by_default() {
+            let mut config = Config::new(admin());
+            assert!(!config.get_require_registration());
+            assert_eq!(config.update_require_registration(true, user()), Err(Error::NotAdmin));
+            assert_eq!(config.update_require_registration(true, admin()), Ok(()));
+            assert!(config.get_require_registration());
+        }
+    }
+}
+
+/// Rounding direction for integer division in protocol math
+/// Policy: amounts owed by borrowers round `Up`, amounts credited to suppliers round `Down`,
+/// so truncation dust always stays with the protocol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// Compute `value * numerator / denominator` with an explicit rounding direction
+/// Returns None on overflow or a zero denominator
+pub fn mul_div(value: u128, numerator: u128, denominator: u128, rounding: Rounding) -> Option<u128> {
+    if denominator == 0 {
+        return None;
+    }
+    let product = value.checked_mul(numerator)?;
+    let quotient = product / denominator;
+    match rounding {
+        Rounding::Up if product % denominator != 0 => quotient.checked_add(1),
+        _ => Some(quotient),
+    }
+}
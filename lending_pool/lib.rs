@@ -4,6 +4,8 @@ use ink::env::{DefaultEnvironment, Environment};
 
 pub type AccountId = <DefaultEnvironment as Environment>::AccountId;
 
+pub use self::lending_pool::Error as LendingPoolError;
+
 /// The lending pool contract is used to manage the pool with auto rate in accruals.
 /// As Kleo uses a loan manager contract to handle loans, this contract will mainly
 /// provide the pool where the contracts are created from, and handle certain calculations.
@@ -11,44 +13,188 @@ pub type AccountId = <DefaultEnvironment as Environment>::AccountId;
 #[ink::contract]
 mod lending_pool {
     use ink::storage::Mapping;
-    use config::ConfigRef;
+    use config::{mul_div, ConfigRef, Rounding};
     use ink::storage::Lazy;
     use ink::U256;
     use ink::primitives::AccountIdMapper;
+    use ink::prelude::vec::Vec;
+
+    /// Role a contract is authorized for in `authorized`
+    #[ink::storage_item(packed)]
+    #[derive(Debug, PartialEq, Eq, Copy, Clone)]
+    pub enum CallerRole {
+        LoanManager,
+        Vouch,
+    }
+
+    /// Config parameters the LendingPool contract reads, as returned by `dependent_config`
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct DependentConfig {
+        pub admin: AccountId,
+        pub base_interest_rate: u64,
+        pub optimal_utilization: u64,
+        pub slope1: u64,
+        pub slope2: u64,
+        pub max_rate: u64,
+        pub rate_scale: u64,
+        pub rate_step: u64,
+        pub reserve_factor_tiers: (u8, u8, u64),
+        pub keeper_fee_percent: u8,
+        pub global_borrow_cap: Balance,
+        pub min_liquidity_buffer: Balance,
+        pub min_accrual_utilization: u64,
+        pub slash_delay: Timestamp,
+        pub smoothing_factor: u32,
+        pub withdraw_cooldown: Timestamp,
+        pub reserve_lending_percent: u8,
+    }
 
     /// All information that is needed to store in the contract
     #[ink(storage)]
     pub struct LendingPool{
         config: ConfigRef, // Contract address of Config
+        asset_id: u32, // Identifier of the asset this pool instance lends
         total_liquidity: Lazy<Balance>,
         total_borrowed: Lazy<Balance>,
         reserved_funds: Lazy<Balance>,
+        keeper_pool: Lazy<Balance>, // Interest set aside for keeper bounties (18 decimals)
         total_principal_deposits: Lazy<Balance>, // Total principal deposited (excluding interest)
         user_deposits: Mapping<AccountId, Balance>,
         user_staked_capital: Mapping<AccountId, Balance>, // Staked capital per user (in 10 decimals)
         last_update: Lazy<Timestamp>,
         vouch_contract: Lazy<Option<Address>>, // Authorized vouch contract address
         loan_manager: Lazy<Option<Address>>, // Authorized loan manager contract address
+        authorized: Mapping<Address, CallerRole>, // Contracts allowed to call gated messages, by role
+        base_rate_adjustment: Lazy<i64>, // Offset applied to the Config base rate by rebalance_base_rate
+        migration_target: Lazy<Option<Address>>, // Successor pool receiving exported deposits
+        pending_slashes: Mapping<AccountId, (Balance, Balance, Timestamp)>, // user -> (slash awaiting commit, loan amount it covers, scheduled at)
+        entered: Lazy<bool>, // Reentrancy guard for value-moving messages
+        smoothed_rate: Lazy<u64>, // Rate interest accrues at when smoothing is on (0 = not yet set)
+        cumulative_interest: Lazy<Balance>, // Gross interest accrued over the pool's lifetime (18 decimals)
+        cash_balance: Lazy<Balance>, // Native balance moved in and out by the pool's own messages (18 decimals)
+        reserves_lent: Lazy<Balance>, // Part of total_borrowed drawn from lendable reserves (18 decimals)
+        last_deposit_time: Mapping<AccountId, Timestamp>, // user -> time of their latest deposit
     }
 
     /// Events for lending pool actions
     #[ink(event)]
     pub struct Deposit {
+        asset_id: u32,
         depositor: AccountId,
         amount: Balance
     }
 
     #[ink(event)]
     pub struct Withdraw {
+        asset_id: u32,
         withdrawer: AccountId,
         amount: Balance,
     }
 
     #[ink(event)]
     pub struct RepaymentReceived {
+        asset_id: u32,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct DepositExported {
+        asset_id: u32,
+        user: AccountId,
+        target: Address,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ReservesFunded {
+        asset_id: u32,
+        amount: Balance,
+        total_reserves: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ReservesDistributed {
+        asset_id: u32,
+        recipient: Address,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct KeeperBountyPaid {
+        asset_id: u32,
+        keeper: Address,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Reconciled {
+        asset_id: u32,
+        surplus: Balance,
+        deficit: Balance,
+    }
+
+    #[ink(event)]
+    pub struct SlashScheduled {
+        asset_id: u32,
+        user: AccountId,
+        amount: Balance,
+        loan_amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct SlashCommitted {
+        asset_id: u32,
+        user: AccountId,
         amount: Balance,
     }
 
+    #[ink(event)]
+    pub struct SlashCancelled {
+        asset_id: u32,
+        user: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct BaseRateRebalanced {
+        asset_id: u32,
+        adjustment: i64,
+        effective_base_rate: u64,
+    }
+
+    /// Config parameters behind the rate curve and utilization, read once per message
+    #[derive(Debug, Clone, Copy)]
+    struct RateParams {
+        base_interest_rate: u64,
+        optimal_utilization: u64,
+        slope1: u64,
+        slope2: u64,
+        max_rate: u64,
+        rate_scale: u64,
+        smoothing_factor: u32,
+        reserve_lending_percent: u8,
+    }
+
+    /// Config parameters behind interest accrual, read once per message
+    #[derive(Debug, Clone, Copy)]
+    struct AccrualParams {
+        rate: RateParams,
+        min_accrual_utilization: u64,
+        reserve_factor_tiers: (u8, u8, u64),
+        keeper_fee_percent: u8,
+    }
+
+    /// Config limits a disbursement is checked against, read once per message
+    #[derive(Debug, Clone, Copy)]
+    struct DisburseLimits {
+        reserve_lending_percent: u8,
+        /// 10 decimals, 0 = no buffer
+        min_liquidity_buffer: Balance,
+        /// 10 decimals, 0 = unlimited
+        global_borrow_cap: Balance,
+    }
+
     /// User information structure
     #[derive(Debug, Clone, PartialEq, Eq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
@@ -77,12 +223,28 @@ mod lending_pool {
         TransactionFailed,
         AmountMismatch,
         Unauthorized,
+        FundsEncumbered,
+        GlobalCapExceeded,
+        NoSurplus,
+        BufferViolation,
+        NoPendingSlash,
+        SlashDelayActive,
+        SlashAlreadyPending,
+        Reentrancy,
+        WithdrawCooldownActive,
     }
 
     impl LendingPool {
         // Decimal conversion constant: 10^8 to convert between 10 decimals and 18 decimals
         const DECIMAL_CONVERSION: u128 = 100_000_000; // 10^8
 
+        // Yearly denominator for scaled rates (assuming rates are in "per year" basis)
+        // 365.25 days * 24 hours * 60 min * 60 sec * 1000 ms ≈ 31_557_600_000 ms
+        const YEAR_MS: u128 = 31_557_600_000u128;
+
+        // Scale of the exchange rate between deposited principal and total liquidity (1e18 = 1:1)
+        const EXCHANGE_RATE_SCALE: u128 = 1_000_000_000_000_000_000; // 1e18
+
         /// Convert amount from 18 decimals (chain format) to 10 decimals (storage format)
         fn convert_18_to_10_decimals(&self, amount_18: Balance) -> Balance {
             (amount_18 as u128)
@@ -97,8 +259,9 @@ mod lending_pool {
                 .unwrap_or(0) as Balance
         }
 
+        /// `asset_id` identifies the asset lent by this pool instance and is included in every event
         #[ink(constructor)]
-        pub fn new(config_address: Address) -> Self {
+        pub fn new(config_address: Address, asset_id: u32) -> Self {
             let config =
                 ink::env::call::FromAddr::from_addr(config_address);
             let block_timestamp = Self::env().block_timestamp();
@@ -106,15 +269,27 @@ mod lending_pool {
             // Make this a mutable instance to set last_update to the latest block timestamp
             let mut instance = Self {
                 config,
+                asset_id,
                 total_liquidity: Lazy::new(),
                 total_borrowed: Lazy::new(),
                 reserved_funds: Lazy::new(),
+                keeper_pool: Lazy::new(),
                 total_principal_deposits: Lazy::new(),
                 user_deposits: Mapping::default(),
                 user_staked_capital: Mapping::default(),
                 last_update: Lazy::new(),
                 vouch_contract: Lazy::default(),
                 loan_manager: Lazy::default(),
+                authorized: Mapping::default(),
+                base_rate_adjustment: Lazy::new(),
+                migration_target: Lazy::default(),
+                pending_slashes: Mapping::default(),
+                entered: Lazy::default(),
+                smoothed_rate: Lazy::default(),
+                cumulative_interest: Lazy::default(),
+                cash_balance: Lazy::default(),
+                reserves_lent: Lazy::default(),
+                last_deposit_time: Mapping::default(),
             };
             instance.last_update.set(&block_timestamp);
             instance
@@ -129,6 +304,7 @@ mod lending_pool {
                 return Err(Error::Unauthorized);
             }
             self.vouch_contract.set(&Some(vouch_address));
+            self.authorized.insert(vouch_address, &CallerRole::Vouch);
             Ok(())
         }
 
@@ -141,28 +317,106 @@ mod lending_pool {
                 return Err(Error::Unauthorized);
             }
             self.loan_manager.set(&Some(loan_manager_address));
+            self.authorized.insert(loan_manager_address, &CallerRole::LoanManager);
+            Ok(())
+        }
+
+        /// Authorize an additional contract to call messages gated to `role`
+        /// Only callable by the Config admin (checked against the transaction caller). Re-adding an address replaces its role.
+        #[ink(message)]
+        pub fn add_authorized(&mut self, addr: Address, role: CallerRole) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.authorized.insert(addr, &role);
+            Ok(())
+        }
+
+        /// Revoke a contract's authorization (e.g., a compromised or deprecated dependency)
+        /// Only callable by the Config admin. Also applies to the addresses registered at setup.
+        #[ink(message)]
+        pub fn remove_authorized(&mut self, addr: Address) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.authorized.remove(addr);
+            Ok(())
+        }
+
+        /// Check whether an address is currently authorized for any role
+        #[ink(message)]
+        pub fn is_authorized(&self, addr: Address) -> bool {
+            self.authorized.contains(addr)
+        }
+
+        /// Get the identifier of the asset this pool lends
+        #[ink(message)]
+        pub fn get_asset_id(&self) -> u32 {
+            self.asset_id
+        }
+
+        /// Get the address of the Config contract this contract reads parameters from
+        #[ink(message)]
+        pub fn get_config_address(&self) -> Address {
+            ink::ToAddr::to_addr(&self.config)
+        }
+
+        /// Get every Config parameter this contract consumes, read through its ConfigRef
+        /// Lets integrators check the contract sees the expected values
+        #[ink(message)]
+        pub fn dependent_config(&self) -> DependentConfig {
+            DependentConfig {
+                admin: self.config.get_admin(),
+                base_interest_rate: self.config.get_base_interest_rate(),
+                optimal_utilization: self.config.get_optimal_utilization(),
+                slope1: self.config.get_slope1(),
+                slope2: self.config.get_slope2(),
+                max_rate: self.config.get_max_rate(),
+                rate_scale: self.config.get_rate_scale(),
+                rate_step: self.config.get_rate_step(),
+                reserve_factor_tiers: self.config.get_reserve_factor_tiers(),
+                keeper_fee_percent: self.config.get_keeper_fee_percent(),
+                global_borrow_cap: self.config.get_global_borrow_cap(),
+                min_liquidity_buffer: self.config.get_min_liquidity_buffer(),
+                min_accrual_utilization: self.config.get_min_accrual_utilization(),
+                slash_delay: self.config.get_slash_delay(),
+                smoothing_factor: self.config.get_smoothing_factor(),
+                withdraw_cooldown: self.config.get_withdraw_cooldown(),
+                reserve_lending_percent: self.config.get_reserve_lending_percent(),
+            }
+        }
+
+        /// Internal helper to check the transaction caller is the Config admin
+        /// Uses env().caller() rather than a caller-supplied id, which anyone could forge
+        fn ensure_admin(&self) -> Result<(), Error> {
+            let admin = self.config.get_admin();
+            if Self::env().caller() != AccountIdMapper::to_address(admin.as_ref()) {
+                return Err(Error::Unauthorized);
+            }
             Ok(())
         }
 
         /// Internal helper to check if caller is the authorized vouch contract
         fn ensure_vouch_contract(&self) -> Result<(), Error> {
             let caller = Self::env().caller();
-            let vouch_contract = self.vouch_contract.get()
-                .and_then(|opt| opt)
-                .ok_or(Error::Unauthorized)?;
-            if caller != vouch_contract {
+            if self.authorized.get(caller) != Some(CallerRole::Vouch) {
                 return Err(Error::Unauthorized);
             }
             Ok(())
         }
 
+        /// Internal helper to run a value-moving message with reentrancy rejected
+        /// A nested call into any guarded message fails with Reentrancy until `f` returns.
+        fn non_reentrant<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T, Error>) -> Result<T, Error> {
+            if self.entered.get_or_default() {
+                return Err(Error::Reentrancy);
+            }
+            self.entered.set(&true);
+            let result = f(self);
+            self.entered.set(&false);
+            result
+        }
+
         /// Internal helper to check if caller is the authorized loan manager
         fn ensure_loan_manager(&self) -> Result<(), Error> {
             let caller = Self::env().caller();
-            let loan_manager = self.loan_manager.get()
-                .and_then(|opt| opt)
-                .ok_or(Error::Unauthorized)?;
-            if caller != loan_manager {
+            if self.authorized.get(caller) != Some(CallerRole::LoanManager) {
                 return Err(Error::Unauthorized);
             }
             Ok(())
@@ -181,6 +435,8 @@ mod lending_pool {
             }
             let deposited_18: Balance = deposited_u256.as_u128(); // 18 decimals from chain
 
+            self.credit_cash(deposited_18);
+
             let caller_acc = account_id;
 
             // Convert to 10 decimals for user_deposits storage
@@ -193,6 +449,12 @@ mod lending_pool {
             
             // Verify the insert worked (read back immediately)
             let verified_balance = self.user_deposits.get(&caller_acc).unwrap_or(0);
+
+            // Start the withdrawal cooldown, unless someone else deposits on the user's behalf:
+            // a third party must not be able to lock the user's withdrawals
+            if self.env().caller() == AccountIdMapper::to_address(caller_acc.as_ref()) {
+                self.last_deposit_time.insert(&caller_acc, &self.env().block_timestamp());
+            }
             
             // Update total liquidity (stored in 18 decimals)
             let mut total_liquidity = self.total_liquidity.get_or_default();
@@ -206,6 +468,7 @@ mod lending_pool {
 
             // Emit deposit event (use 18 decimals for consistency)
             self.env().emit_event(Deposit {
+                asset_id: self.asset_id,
                 depositor: caller_acc,
                 amount: deposited_18,
             });
@@ -217,15 +480,52 @@ mod lending_pool {
         /// amount: in 10 decimals (storage format)
         #[ink(message)]
         pub fn withdraw(&mut self, amount: Balance, account_id: AccountId) -> Result<(), Error> {
-            let caller_acc = account_id;
-            if amount == 0 {
+            self.non_reentrant(|pool| pool.do_withdraw(Some(amount), account_id)).map(|_| ())
+        }
+
+        /// Withdraw everything the user can: their share not backing vouches, capped at `get_available_deposit`
+        /// Returns the amount withdrawn in 10 decimals (storage format)
+        #[ink(message)]
+        pub fn withdraw_all(&mut self, account_id: AccountId) -> Result<Balance, Error> {
+            self.non_reentrant(|pool| pool.do_withdraw(None, account_id))
+        }
+
+        /// Internal: Body of `withdraw` and `withdraw_all`, run under the reentrancy guard
+        fn do_withdraw(&mut self, amount: Option<Balance>, account_id: AccountId) -> Result<Balance, Error> {
+            if amount == Some(0) {
                 return Err(Error::ZeroAmount);
             }
 
-            self.accrue_interest();
+            // The cooldown is only read for users it can apply to
+            let withdraw_cooldown = if self.last_deposit_time.contains(&account_id) {
+                self.config.get_withdraw_cooldown()
+            } else {
+                0
+            };
+            let params = self.accrual_params();
+            self.withdraw_with(amount, account_id, withdraw_cooldown, &params)
+        }
 
-            // Convert amount from 10 decimals to 18 decimals for calculations
-            let amount_18 = self.convert_10_to_18_decimals(amount);
+        /// Internal: Withdraw `amount` (10 decimals), or everything available when None, on Config
+        /// values the caller already read. Returns the amount withdrawn in 10 decimals
+        fn withdraw_with(
+            &mut self,
+            amount: Option<Balance>,
+            account_id: AccountId,
+            withdraw_cooldown: Timestamp,
+            params: &AccrualParams,
+        ) -> Result<Balance, Error> {
+            let caller_acc = account_id;
+
+            // Deter flash deposit-and-withdraw; the deposit keeps earning interest meanwhile
+            if let Some(last_deposit) = self.last_deposit_time.get(&caller_acc) {
+                let now = self.env().block_timestamp();
+                if now.saturating_sub(last_deposit) < withdraw_cooldown {
+                    return Err(Error::WithdrawCooldownActive);
+                }
+            }
+
+            self.accrue_interest_with(params);
 
             let user_deposit = self.user_deposits.get(&caller_acc).unwrap_or(0);
             let total_liquidity = self.total_liquidity.get_or_default();
@@ -238,30 +538,33 @@ mod lending_pool {
             let user_staked_10 = self.user_staked_capital.get(&caller_acc).unwrap_or(0);
             let user_staked_18 = self.convert_10_to_18_decimals(user_staked_10);
             
-            // Calculate user's share of the pool (principal + interest)
-            // User can withdraw up to their share: (user_deposit / total_principal) * total_liquidity
-            // If total_liquidity >= total_principal, user_share should be >= user_deposit_18
-            // If total_liquidity < total_principal (loans disbursed), user_share will be < user_deposit_18
-            let user_share = if total_principal > 0 && total_liquidity > 0 {
-                // Calculate user's share with overflow protection
-                // If calculation overflows, fall back to user_deposit_18 (at least their principal)
-                (user_deposit_18 as u128)
-                    .checked_mul(total_liquidity as u128)
-                    .and_then(|v| v.checked_div(total_principal as u128))
-                    .unwrap_or(user_deposit_18) as Balance
-            } else {
-                user_deposit_18 // Fallback if no principal or liquidity
+            // User can withdraw up to their share of the pool (principal + interest)
+            let user_share = self.calculate_user_share(user_deposit_18, total_liquidity, total_principal);
+
+            // withdraw_all takes the unencumbered share, never more than the unencumbered principal
+            let amount = match amount {
+                Some(amount) => amount,
+                None => self
+                    .convert_18_to_10_decimals(user_share.saturating_sub(user_staked_18))
+                    .min(self.get_available_deposit(caller_acc)),
             };
-            
-            // Cap user_share at total_liquidity (can't withdraw more than what's in the pool)
-            let user_share = user_share.min(total_liquidity);
-            
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            // Convert amount from 10 decimals to 18 decimals for calculations
+            let amount_18 = self.convert_10_to_18_decimals(amount);
+
+            if amount_18 > user_share {
+                return Err(Error::UnavailableFunds);
+            }
+
             // Calculate available balance: user_share minus staked capital
             // Users cannot withdraw staked capital that's backing active vouches
             let available_balance = user_share.saturating_sub(user_staked_18);
             
             if amount_18 > available_balance {
-                return Err(Error::UnavailableFunds);
+                return Err(Error::FundsEncumbered);
             }
             
             // Calculate the user's share of the pool (principal + interest)
@@ -295,44 +598,99 @@ mod lending_pool {
             total_principal = total_principal.saturating_sub(principal_to_reduce_18);
             self.total_principal_deposits.set(&total_principal);
 
+            self.debit_cash(amount_18);
+
             // Transfer in 18 decimals (chain format)
             if self.env().transfer(AccountIdMapper::to_address(caller_acc.as_ref()), U256::from(amount_18)).is_err() {
                 return Err(Error::TransactionFailed);
             }
 
             self.env().emit_event(Withdraw {
+                asset_id: self.asset_id,
                 withdrawer: caller_acc,
                 amount: amount_18, // Emit in 18 decimals
             });
 
-            Ok(())
+            Ok(amount)
         }
 
         #[ink(message)]
         pub fn get_current_rate(&self) -> u64 {
-            let total_liquidity = self.total_liquidity.get_or_default();
-            if total_liquidity == 0 {
-                return self.config.get_base_interest_rate();
+            self.current_rate(&self.rate_params())
+        }
+
+        /// Internal: Spot rate for the pool's current utilization
+        fn current_rate(&self, params: &RateParams) -> u64 {
+            if self.total_liquidity.get_or_default() == 0 {
+                return self.effective_base_rate(params);
             }
+            self.calculate_rate(params, self.utilization_percent(params))
+        }
 
-            let total_borrowed = self.total_borrowed.get_or_default();
+        /// Explain the current rate as (utilization, rate, above_optimal)
+        /// Utilization is a percentage scaled by 1e9 and capped at 100%, as fed to the slope math and
+        /// on the scale of optimal_utilization; above_optimal tells whether the steeper slope2 segment is in effect.
+        #[ink(message)]
+        pub fn rate_breakdown(&self) -> (u64, u64, bool) {
+            let params = self.rate_params();
+            let utilization = self.utilization_percent(&params);
+            let optimal = params.optimal_utilization;
+            let above_optimal = optimal > 0 && utilization > optimal;
+            (utilization, self.current_rate(&params), above_optimal)
+        }
 
-            // Utilization = borrowed / liquidity, scaled by 1e9 for precision
-            // Use checked arithmetic to prevent overflow traps when values are very large
-            let utilization = (total_borrowed as u128)
-                .checked_mul(1_000_000_000u128)
-                .and_then(|v| v.checked_div(total_liquidity as u128))
-                .unwrap_or(0) as u64;
-            
-            // Cap utilization at 1e9 (100%) to prevent issues with calculation overflow
+        /// Get the current utilization as a percentage scaled by 1e9 (80% = 80_000_000_000)
+        #[ink(message)]
+        pub fn get_utilization(&self) -> u64 {
+            self.utilization_percent(&self.rate_params())
+        }
+
+        /// Preview the interest rate at an arbitrary utilization, a percentage scaled by 1e9 like
+        /// `get_utilization` and Config's optimal_utilization (80% = 80_000_000_000)
+        /// Runs the same slope math as `get_current_rate`, independent of the pool's state
+        #[ink(message)]
+        pub fn rate_at_utilization(&self, utilization: u64) -> u64 {
+            self.calculate_rate(&self.rate_params(), utilization)
+        }
+
+        /// Internal: Read the Config parameters the rate curve depends on
+        /// Fetched once per message so the slope math itself makes no cross-contract calls
+        fn rate_params(&self) -> RateParams {
+            RateParams {
+                base_interest_rate: self.config.get_base_interest_rate(),
+                optimal_utilization: self.config.get_optimal_utilization(),
+                slope1: self.config.get_slope1(),
+                slope2: self.config.get_slope2(),
+                max_rate: self.config.get_max_rate(),
+                rate_scale: self.config.get_rate_scale(),
+                smoothing_factor: self.config.get_smoothing_factor(),
+                reserve_lending_percent: self.config.get_reserve_lending_percent(),
+            }
+        }
+
+        /// Internal: Read the Config parameters interest accrual depends on, including the rate curve's
+        fn accrual_params(&self) -> AccrualParams {
+            AccrualParams {
+                rate: self.rate_params(),
+                min_accrual_utilization: self.config.get_min_accrual_utilization(),
+                reserve_factor_tiers: self.config.get_reserve_factor_tiers(),
+                keeper_fee_percent: self.config.get_keeper_fee_percent(),
+            }
+        }
+
+        /// Internal: Rate curve shared by `get_current_rate` and `rate_at_utilization`
+        /// utilization is on the `utilization_percent` scale, the one optimal_utilization uses
+        fn calculate_rate(&self, params: &RateParams, utilization: u64) -> u64 {
+            // Cap utilization at 100% to prevent issues with calculation overflow
             // This protects against cases where stored values are incorrectly scaled
-            let utilization = utilization.min(1_000_000_000u64);
+            let hundred_percent = params.rate_scale.saturating_mul(100);
+            let utilization = utilization.min(hundred_percent);
 
-            let base = self.config.get_base_interest_rate();
-            let optimal = self.config.get_optimal_utilization();
-            let slope1 = self.config.get_slope1();
-            let slope2 = self.config.get_slope2();
-            let max_rate = self.config.get_max_rate();
+            let base = self.effective_base_rate(params);
+            let optimal = params.optimal_utilization;
+            let slope1 = params.slope1;
+            let slope2 = params.slope2;
+            let max_rate = params.max_rate;
 
             // Safety check: if optimal is 0, return base rate to prevent division by zero
             if optimal == 0 {
@@ -348,9 +706,9 @@ mod lending_pool {
                     .unwrap_or(0) as u64;
                 base.saturating_add(additional)
             } else {
-                // base + slope1 + ((utilization - optimal) / (1e9 - optimal)) * slope2
+                // base + slope1 + ((utilization - optimal) / (100% - optimal)) * slope2
                 let excess = utilization.saturating_sub(optimal);
-                let max_excess = 1_000_000_000u64.saturating_sub(optimal);
+                let max_excess = hundred_percent.saturating_sub(optimal);
                 let additional = if max_excess == 0 {
                     0
                 } else {
@@ -367,54 +725,154 @@ mod lending_pool {
             rate.min(max_rate)
         }
 
-        /// Internal function to get accrued interest since last update
-        /// If enough time has passed, it will update total liquidity and reserved funds
-        fn accrue_interest(&mut self) {
-            let current_time = self.env().block_timestamp();
-            let last = self.last_update.get_or_default();
-            let elapsed = current_time.saturating_sub(last);
-            if elapsed == 0 {
-                return;
+        /// Nudge the base rate toward keeping utilization at the optimal level
+        /// Only callable by the Config admin. Over-utilization raises the base rate and
+        /// under-utilization lowers it, by at most `rate_step` per call. The resulting
+        /// effective base rate is kept within [0, max_rate].
+        #[ink(message)]
+        pub fn rebalance_base_rate(&mut self) -> Result<(), Error> {
+            self.ensure_admin()?;
+
+            let params = self.accrual_params();
+            let rate_step = self.config.get_rate_step();
+            self.apply_rebalance(&params, rate_step);
+            Ok(())
+        }
+
+        /// Internal: Body of `rebalance_base_rate` past the admin check, on Config values the
+        /// caller already read
+        fn apply_rebalance(&mut self, params: &AccrualParams, rate_step: u64) {
+            self.accrue_interest_with(params);
+
+            let utilization = self.utilization_percent(&params.rate);
+            let optimal = params.rate.optimal_utilization;
+
+            let step = rate_step.min(i64::MAX as u64) as i64;
+            let mut adjustment = self.base_rate_adjustment.get_or_default();
+            if utilization > optimal {
+                adjustment = adjustment.saturating_add(step);
+            } else if utilization < optimal {
+                adjustment = adjustment.saturating_sub(step);
             }
 
+            // Keep the effective base rate within [0, max_rate]
+            let base = params.rate.base_interest_rate.min(i64::MAX as u64) as i64;
+            let max_rate = params.rate.max_rate.min(i64::MAX as u64) as i64;
+            adjustment = adjustment.clamp(base.saturating_neg(), max_rate.saturating_sub(base).max(0));
+            self.base_rate_adjustment.set(&adjustment);
+
+            self.env().emit_event(BaseRateRebalanced {
+                asset_id: self.asset_id,
+                adjustment,
+                effective_base_rate: self.effective_base_rate(&params.rate),
+            });
+        }
+
+        /// Internal: Utilization as borrowed / lendable base, a percentage scaled by the rate scale
+        /// (80% = 80 * 1e9), the scale of Config's optimal_utilization and reserve threshold; capped at 100%.
+        /// The single utilization measure for rates, rebalancing, reserves and accrual.
+        fn utilization_percent(&self, params: &RateParams) -> u64 {
+            let total_liquidity = self.lendable_base(params.reserve_lending_percent);
+            if total_liquidity == 0 {
+                return 0;
+            }
             let total_borrowed = self.total_borrowed.get_or_default();
-            if total_borrowed == 0 {
-                self.last_update.set(&current_time);
-                return;
+            let hundred_percent = (params.rate_scale as u128).saturating_mul(100);
+            (total_borrowed as u128)
+                .checked_mul(hundred_percent)
+                .and_then(|v| v.checked_div(total_liquidity as u128))
+                .unwrap_or(0)
+                .min(hundred_percent) as u64
+        }
+
+        /// Get the current offset applied to the Config base rate (scaled by 1e9 per percent)
+        #[ink(message)]
+        pub fn get_base_rate_adjustment(&self) -> i64 {
+            self.base_rate_adjustment.get_or_default()
+        }
+
+        /// Internal: Config base rate plus the rebalance adjustment, floored at 0
+        fn effective_base_rate(&self, params: &RateParams) -> u64 {
+            let base = params.base_interest_rate;
+            let adjustment = self.base_rate_adjustment.get_or_default();
+            if adjustment >= 0 {
+                base.saturating_add(adjustment as u64)
+            } else {
+                base.saturating_sub(adjustment.unsigned_abs())
             }
+        }
+
+        /// Get the constants used by interest accrual as (year_ms, rate_scale)
+        /// Accrual computes interest = borrowed * rate / (100 * rate_scale) * elapsed_ms / year_ms, where
+        /// rate is percent * rate_scale and each division rounds down, so off-chain projections can
+        /// reproduce it exactly
+        #[ink(message)]
+        pub fn accrual_constants(&self) -> (u128, u64) {
+            (Self::YEAR_MS, self.config.get_rate_scale())
+        }
 
-            // Get current dynamic rate (same logic as get_current_rate)
-            let rate = self.get_current_rate(); // Reuses the public logic
+        /// Get the per-second interest rate used by accrual
+        /// The annual accrual rate (`get_current_rate`, or the smoothed rate when smoothing is on,
+        /// scaled by rate_scale) is divided by the year constant and multiplied by an extra rate_scale
+        /// of precision, so: annual_rate ≈ rate_per_second * (YEAR_MS / 1000) / rate_scale
+        #[ink(message)]
+        pub fn get_rate_per_second(&self) -> u128 {
+            let params = self.rate_params();
+            Self::rate_per_second(self.accrual_rate(&params), params.rate_scale)
+        }
 
-            // Yearly denominator for scaled rates (assuming rates are in "per year" basis)
-            // 365.25 days * 24 hours * 60 min * 60 sec * 1000 ms ≈ 31_557_600_000 ms
-            const YEAR_MS: u128 = 31_557_600_000u128;
+        /// Internal: Per-second form of an annual rate, with rate_scale of extra precision
+        fn rate_per_second(annual_rate: u64, rate_scale: u64) -> u128 {
+            (annual_rate as u128)
+                .saturating_mul(1000)
+                .saturating_mul(rate_scale as u128)
+                / Self::YEAR_MS
+        }
 
-            // interest = borrowed * rate * elapsed_ms / YEAR_MS
-            // All values scaled appropriately (rate already scaled by 1e9, e.g., 5% = 5_000_000_000, 10% = 10_000_000_000)
-            let interest = (total_borrowed as u128)
-                .checked_mul(rate as u128)
-                .and_then(|v| v.checked_mul(elapsed as u128))
-                .and_then(|v| v.checked_div(YEAR_MS))
-                .unwrap_or(0) as Balance;
+        /// Internal function to get accrued interest since last update
+        /// If enough time has passed, it will update total liquidity and reserved funds
+        fn accrue_interest(&mut self) {
+            let params = self.accrual_params();
+            self.accrue_interest_with(&params);
+        }
+
+        /// Internal: Body of `accrue_interest`, on Config parameters the caller already read
+        fn accrue_interest_with(&mut self, params: &AccrualParams) {
+            let current_time = self.env().block_timestamp();
+            let (interest, reserve_add) = self.project_interest(params, current_time);
 
             if interest == 0 {
+                self.update_smoothed_rate(&params.rate);
                 self.last_update.set(&current_time);
                 return;
             }
 
-            // Add interest to total liquidity
+            // Set aside the keeper fee, which funds bounties for processing defaults
+            let keeper_fee_percent = params.keeper_fee_percent;
+            let keeper_add = mul_div(interest as u128, keeper_fee_percent as u128, 100, Rounding::Down)
+                .unwrap_or(0)
+                .min(interest.saturating_sub(reserve_add) as u128) as Balance;
+            let keeper_pool = self.keeper_pool.get_or_default();
+            self.keeper_pool.set(&keeper_pool.saturating_add(keeper_add));
+
+            // Add interest to total liquidity, except the reserve portion and the keeper fee: both are
+            // tracked apart from total_liquidity, so paying them out never leaves liquidity behind
             let mut liquidity = self.total_liquidity.get_or_default();
-            liquidity = liquidity.saturating_add(interest);
+            liquidity = liquidity.saturating_add(interest.saturating_sub(reserve_add).saturating_sub(keeper_add));
             self.total_liquidity.set(&liquidity);
 
+            // Lifetime income, before reserves and keeper fees
+            let cumulative = self.cumulative_interest.get_or_default();
+            self.cumulative_interest.set(&cumulative.saturating_add(interest));
+
             // Skim reserve factor
-            let reserve_factor = self.config.get_reserve_factor(); // e.g., 10
-            let reserve_add = interest.saturating_mul(reserve_factor as Balance) / 100;
             let mut reserves = self.reserved_funds.get_or_default();
             reserves = reserves.saturating_add(reserve_add);
             self.reserved_funds.set(&reserves);
 
+            // Move the smoothed rate towards the rate at the new utilization
+            self.update_smoothed_rate(&params.rate);
+
             // Update timestamp
             self.last_update.set(&current_time);
 
@@ -422,6 +880,161 @@ mod lending_pool {
             // self.env().emit_event(InterestAccrued { amount: interest, reserves: reserve_add });
         }
 
+        /// Get the gross interest accrued since deployment (18 decimals)
+        /// Only ever grows, independent of later withdrawals or defaults
+        #[ink(message)]
+        pub fn get_cumulative_interest(&self) -> Balance {
+            self.cumulative_interest.get_or_default()
+        }
+
+        /// Get the smoothed rate interest currently accrues at (0 while smoothing is off or unset)
+        #[ink(message)]
+        pub fn get_smoothed_rate(&self) -> u64 {
+            self.smoothed_rate.get_or_default()
+        }
+
+        /// Get the rate interest accrues at: the smoothed rate while smoothing is on, else the spot rate
+        /// The rate loans originate at, so a momentary utilization spike doesn't lock borrowers in
+        #[ink(message)]
+        pub fn get_accrual_rate(&self) -> u64 {
+            self.accrual_rate(&self.rate_params())
+        }
+
+        /// Internal: Rate interest accrues at
+        /// With smoothing on, the stored moving blend instead of the spot `get_current_rate`
+        fn accrual_rate(&self, params: &RateParams) -> u64 {
+            let smoothed = self.smoothed_rate.get_or_default();
+            if params.smoothing_factor == 0 || smoothed == 0 {
+                return self.current_rate(params);
+            }
+            smoothed
+        }
+
+        /// Internal: Blend the stored rate with the fresh spot rate, weighted by Config's smoothing_factor
+        /// smoothed = (previous * factor + fresh * (100 - factor)) / 100; the first update takes the
+        /// fresh rate as is. Clears the stored rate while smoothing is disabled.
+        fn update_smoothed_rate(&mut self, params: &RateParams) {
+            let factor = params.smoothing_factor as u128;
+            if factor == 0 {
+                self.smoothed_rate.set(&0);
+                return;
+            }
+            let fresh = self.current_rate(params);
+            let previous = self.smoothed_rate.get_or_default();
+            let smoothed = if previous == 0 {
+                fresh
+            } else {
+                ((previous as u128 * factor + fresh as u128 * (100 - factor)) / 100) as u64
+            };
+            self.smoothed_rate.set(&smoothed);
+        }
+
+        /// Internal: Reserves still available to lend (18 decimals): Config's reserve_lending_percent
+        /// of all reserve capital (held plus lent out), less what is already lent out
+        fn lendable_reserves(&self, reserve_lending_percent: u8) -> Balance {
+            let percent = reserve_lending_percent;
+            let lent = self.reserves_lent.get_or_default();
+            let capital = self.reserved_funds.get_or_default().saturating_add(lent);
+            (mul_div(capital as u128, percent as u128, 100, Rounding::Down)
+                .unwrap_or(0) as Balance)
+                .saturating_sub(lent)
+        }
+
+        /// Internal: Capital utilization is measured against: total liquidity plus lendable reserves
+        /// Accrual credits the reserve portion of interest to reserves only, never to total_liquidity,
+        /// so adding them here counts them once
+        fn lendable_base(&self, reserve_lending_percent: u8) -> Balance {
+            self.total_liquidity.get_or_default().saturating_add(self.lendable_reserves(reserve_lending_percent))
+        }
+
+        /// Internal: Part of `interest` earned by reserves lent out, in proportion to their share
+        /// of total_borrowed. It goes back into reserves instead of to suppliers.
+        fn reserve_interest_share(&self, interest: Balance) -> Balance {
+            let lent = self.reserves_lent.get_or_default();
+            let total_borrowed = self.total_borrowed.get_or_default();
+            if lent == 0 || total_borrowed == 0 {
+                return 0;
+            }
+            mul_div(interest as u128, lent.min(total_borrowed) as u128, total_borrowed as u128, Rounding::Down)
+                .unwrap_or(0) as Balance
+        }
+
+        /// Internal: Interest that would accrue between `last_update` and `current_time`
+        /// Returns (interest, reserve portion of it), both in 18 decimals. Does not mutate state.
+        fn project_interest(&self, params: &AccrualParams, current_time: Timestamp) -> (Balance, Balance) {
+            let last = self.last_update.get_or_default();
+            let elapsed = current_time.saturating_sub(last);
+            if elapsed == 0 {
+                return (0, 0);
+            }
+
+            let total_borrowed = self.total_borrowed.get_or_default();
+            if total_borrowed == 0 {
+                return (0, 0);
+            }
+
+            // Near-zero borrowing would only accrue dust that distorts the exchange rate;
+            // accrue_interest then just advances last_update
+            let utilization = self.utilization_percent(&params.rate);
+            if utilization < params.min_accrual_utilization {
+                return (0, 0);
+            }
+
+            // Get current dynamic rate (same logic as get_current_rate, smoothed if enabled)
+            let rate = self.accrual_rate(&params.rate);
+
+            // interest = borrowed * rate / (100 * rate_scale) * elapsed_ms / YEAR_MS
+            // The rate is percent * rate_scale (e.g., 10% = 10_000_000_000 at 1e9), so the annual
+            // interest comes first and is then pro-rated over the year, which keeps the products in range
+            // Credited to suppliers, so it rounds down
+            let hundred_percent = (params.rate.rate_scale as u128).saturating_mul(100);
+            let interest = mul_div(total_borrowed as u128, rate as u128, hundred_percent, Rounding::Down)
+                .and_then(|annual| mul_div(annual, elapsed as u128, Self::YEAR_MS, Rounding::Down))
+                .unwrap_or(0) as Balance;
+
+            // Reserves lent out earn their share of the interest back into reserves
+            let reserve_earned = self.reserve_interest_share(interest);
+            let supplier_interest = interest.saturating_sub(reserve_earned);
+
+            // Reserve factor portion of the suppliers' interest, e.g., 10 = 10%
+            // The high factor applies above the utilization threshold, building reserves faster when risk is high
+            // Rounds up: the remainder left to suppliers is the side that loses the dust
+            let (low_factor, high_factor, threshold) = params.reserve_factor_tiers;
+            let reserve_factor = if utilization > threshold { high_factor } else { low_factor };
+            let reserve_add = mul_div(supplier_interest as u128, reserve_factor as u128, 100, Rounding::Up)
+                .unwrap_or(0)
+                .min(supplier_interest as u128) as Balance;
+
+            (interest, reserve_add.saturating_add(reserve_earned))
+        }
+
+        /// Get interest accrued since the last update but not yet applied
+        /// Returns (interest, reserve portion) in 18 decimals: the gross interest `accrue_interest`
+        /// would apply if it ran now and the part of it going to reserves (reserve factor skim plus
+        /// what lendable reserves earned). Read-only projection.
+        #[ink(message)]
+        pub fn pending_interest(&self) -> (Balance, Balance) {
+            self.project_interest(&self.accrual_params(), self.env().block_timestamp())
+        }
+
+        /// Internal: A depositor's share of the pool (principal + interest), in 18 decimals
+        /// share = (user_deposit / total_principal) * total_liquidity, capped at total_liquidity
+        /// If total_liquidity >= total_principal, the share is >= the deposit
+        /// If total_liquidity < total_principal (loans disbursed), the share is < the deposit
+        fn calculate_user_share(&self, user_deposit_18: Balance, total_liquidity: Balance, total_principal: Balance) -> Balance {
+            let user_share = if total_principal > 0 && total_liquidity > 0 {
+                // Calculate user's share with overflow protection, rounding down in the pool's favor
+                // If calculation overflows, fall back to user_deposit_18 (at least their principal)
+                mul_div(user_deposit_18 as u128, total_liquidity as u128, total_principal as u128, Rounding::Down)
+                    .unwrap_or(user_deposit_18) as Balance
+            } else {
+                user_deposit_18 // Fallback if no principal or liquidity
+            };
+
+            // Cap user_share at total_liquidity (can't withdraw more than what's in the pool)
+            user_share.min(total_liquidity)
+        }
+
         /// Get user's accrued yield (read-only, doesn't accrue interest)
         /// Returns yield in 18 decimals (chain format)
         /// Note: This calculates yield based on current state without accruing interest.
@@ -489,11 +1102,41 @@ mod lending_pool {
             self.total_liquidity.get_or_default()
         }
 
+        /// Get the balance available for keeper bounties (18 decimals)
+        #[ink(message)]
+        pub fn get_keeper_pool(&self) -> Balance {
+            self.keeper_pool.get_or_default()
+        }
+
+        /// Get total_liquidity per unit of deposited principal, scaled by 1e18 (1e18 = 1:1)
+        /// The pool mints no shares: deposits are tracked as principal, and a depositor's share is
+        /// deposit * principal_exchange_rate / 1e18. Accrued interest raises the rate; principal out
+        /// on loan is not in total_liquidity and lowers it. Returns 1e18 when nothing is deposited.
+        #[ink(message)]
+        pub fn principal_exchange_rate(&self) -> u128 {
+            let total_principal = self.total_principal_deposits.get_or_default();
+            if total_principal == 0 {
+                return Self::EXCHANGE_RATE_SCALE;
+            }
+            mul_div(
+                self.total_liquidity.get_or_default() as u128,
+                Self::EXCHANGE_RATE_SCALE,
+                total_principal as u128,
+                Rounding::Down,
+            )
+            .unwrap_or(Self::EXCHANGE_RATE_SCALE)
+        }
+
         /// Disburse part of liquidity (add a borrow basically)
         /// Only callable by the authorized loan manager contract
         /// amount: in 10 decimals (storage format)
         #[ink(message)]
         pub fn disburse(&mut self, amount: Balance, to: AccountId) -> Result<(), Error> {
+            self.non_reentrant(|pool| pool.do_disburse(amount, to))
+        }
+
+        /// Internal: Body of `disburse`, run under the reentrancy guard
+        fn do_disburse(&mut self, amount: Balance, to: AccountId) -> Result<(), Error> {
             // Verify caller is the authorized loan manager
             self.ensure_loan_manager()?;
 
@@ -501,20 +1144,28 @@ mod lending_pool {
 
             // Convert amount from 10 decimals to 18 decimals for calculations and transfer
             let amount_18 = self.convert_10_to_18_decimals(amount);
+            self.check_disburse(amount_18, &self.disburse_limits())?;
 
             let mut total_borrowed = self.total_borrowed.get_or_default();
             let mut total_liquidity = self.total_liquidity.get_or_default();
 
-            if amount_18 > (total_liquidity - total_borrowed) {
-                return Err(Error::UnavailableFunds);
+            // Draw on lendable reserves only for what liquidity can't cover
+            let from_reserves = amount_18.saturating_sub(total_liquidity.saturating_sub(total_borrowed));
+            if from_reserves > 0 {
+                let reserves = self.reserved_funds.get_or_default();
+                self.reserved_funds.set(&reserves.saturating_sub(from_reserves));
+                let lent = self.reserves_lent.get_or_default();
+                self.reserves_lent.set(&lent.saturating_add(from_reserves));
             }
 
             // Update total liquidity and total borrowed (both in 18 decimals)
             total_borrowed = total_borrowed.saturating_add(amount_18);
-            total_liquidity = total_liquidity.saturating_sub(amount_18);
+            total_liquidity = total_liquidity.saturating_sub(amount_18.saturating_sub(from_reserves));
             self.total_borrowed.set(&total_borrowed);
             self.total_liquidity.set(&total_liquidity);
 
+            self.debit_cash(amount_18);
+
             // Transfer disbursed amount to the borrower (in 18 decimals)
             if self.env().transfer(AccountIdMapper::to_address(to.as_ref()), U256::from(amount_18)).is_err() {
                 return Err(Error::TransactionFailed);
@@ -523,10 +1174,60 @@ mod lending_pool {
             Ok(())
         }
 
+        /// Check whether `disburse` could fund an amount right now (10 decimals)
+        /// Runs the same liquidity, buffer and global cap checks and returns the first failing one's
+        /// error. Read-only: interest accrued by `disburse` only adds liquidity, so an Ok here holds.
+        #[ink(message)]
+        pub fn can_disburse(&self, amount: Balance) -> Result<(), Error> {
+            self.check_disburse(self.convert_10_to_18_decimals(amount), &self.disburse_limits())
+        }
+
+        /// Internal: Read the Config limits a disbursement is checked against
+        fn disburse_limits(&self) -> DisburseLimits {
+            DisburseLimits {
+                reserve_lending_percent: self.config.get_reserve_lending_percent(),
+                min_liquidity_buffer: self.config.get_min_liquidity_buffer(),
+                global_borrow_cap: self.config.get_global_borrow_cap(),
+            }
+        }
+
+        /// Internal: Disbursement checks shared by `disburse` and `can_disburse` (amount in 18 decimals)
+        fn check_disburse(&self, amount_18: Balance, limits: &DisburseLimits) -> Result<(), Error> {
+            let total_borrowed = self.total_borrowed.get_or_default();
+
+            // Lendable reserves top up what liquidity alone can lend
+            let available = self.lendable_base(limits.reserve_lending_percent).saturating_sub(total_borrowed);
+            if amount_18 > available {
+                return Err(Error::UnavailableFunds);
+            }
+
+            // Keep the withdrawal buffer out of reach of borrowers
+            let buffer = self.convert_10_to_18_decimals(limits.min_liquidity_buffer);
+            if available.saturating_sub(amount_18) < buffer {
+                return Err(Error::BufferViolation);
+            }
+
+            // Aggregate risk ceiling across all loans (0 = unlimited)
+            let global_cap = limits.global_borrow_cap;
+            if global_cap > 0
+                && total_borrowed.saturating_add(amount_18) > self.convert_10_to_18_decimals(global_cap)
+            {
+                return Err(Error::GlobalCapExceeded);
+            }
+            Ok(())
+        }
+
         /// Repay a loan (reduce borrowed amount)
         /// amount: in 18 decimals (chain format, matching transferred_value)
         #[ink(message, payable)]
         pub fn receive_repayment(&mut self, amount: Balance) -> Result<(), Error> {
+            self.non_reentrant(|pool| pool.do_receive_repayment(amount))
+        }
+
+        /// Internal: Body of `receive_repayment`, run under the reentrancy guard
+        /// All state writes happen before the event; any future outbound transfer
+        /// (e.g., fee splitting) must go after them, at the end of this function.
+        fn do_receive_repayment(&mut self, amount: Balance) -> Result<(), Error> {
             let received_u256 = self.env().transferred_value();
             if received_u256 == U256::zero() {
                 return Err(Error::ZeroAmount);
@@ -540,6 +1241,8 @@ mod lending_pool {
                 return Err(Error::AmountMismatch);
             }
 
+            self.credit_cash(amount);
+
             self.accrue_interest();
 
             // Update total borrowed and total liquidity
@@ -547,11 +1250,337 @@ mod lending_pool {
             borrowed = borrowed.saturating_sub(amount);
             self.total_borrowed.set(&borrowed);
 
+            // Reserves lent out are paid back first
+            let lent = self.reserves_lent.get_or_default();
+            let to_reserves = amount.min(lent);
+            if to_reserves > 0 {
+                self.reserves_lent.set(&lent.saturating_sub(to_reserves));
+                let reserves = self.reserved_funds.get_or_default();
+                self.reserved_funds.set(&reserves.saturating_add(to_reserves));
+            }
+
             let mut liquidity = self.total_liquidity.get_or_default();
-            liquidity = liquidity.saturating_add(amount);
+            liquidity = liquidity.saturating_add(amount.saturating_sub(to_reserves));
             self.total_liquidity.set(&liquidity);
 
-            self.env().emit_event(RepaymentReceived { amount });
+            self.env().emit_event(RepaymentReceived {
+                asset_id: self.asset_id,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Receive a loan extension fee and add it to the reserves
+        /// Only callable by the authorized loan manager contract
+        /// amount: in 18 decimals (chain format, matching transferred_value)
+        #[ink(message, payable)]
+        pub fn receive_extension_fee(&mut self, amount: Balance) -> Result<(), Error> {
+            // Verify caller is the authorized loan manager
+            self.ensure_loan_manager()?;
+
+            let received_u256 = self.env().transferred_value();
+            if received_u256 == U256::zero() {
+                return Err(Error::ZeroAmount);
+            }
+            if received_u256 > U256::from(u128::MAX) {
+                return Err(Error::Overflow);
+            }
+            let received: Balance = received_u256.as_u128(); // 18 decimals
+
+            if received != amount {
+                return Err(Error::AmountMismatch);
+            }
+
+            self.credit_cash(amount);
+
+            let mut reserves = self.reserved_funds.get_or_default();
+            reserves = reserves.saturating_add(amount);
+            self.reserved_funds.set(&reserves);
+
+            Ok(())
+        }
+
+        /// Set the successor pool that exported deposits are sent to
+        /// Only callable by the Config admin
+        #[ink(message)]
+        pub fn set_migration_target(&mut self, target: Address) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.migration_target.set(&Some(target));
+            Ok(())
+        }
+
+        /// Move a user's whole deposit (principal + interest) to the migration target
+        /// Only callable by the Config admin. Deposits backing active vouches cannot be exported.
+        /// Returns the amount moved in 18 decimals (chain format)
+        #[ink(message)]
+        pub fn export_deposit(&mut self, user: AccountId) -> Result<Balance, Error> {
+            self.ensure_admin()?;
+            let params = self.accrual_params();
+            self.export_deposit_with(user, &params)
+        }
+
+        /// Internal: Body of `export_deposit` past the admin check, on Config parameters the
+        /// caller already read
+        fn export_deposit_with(&mut self, user: AccountId, params: &AccrualParams) -> Result<Balance, Error> {
+            let target = self.migration_target.get()
+                .and_then(|opt| opt)
+                .ok_or(Error::Unauthorized)?;
+
+            let user_deposit = self.user_deposits.get(&user).unwrap_or(0);
+            if user_deposit == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            if self.user_staked_capital.get(&user).unwrap_or(0) > 0 {
+                return Err(Error::FundsEncumbered);
+            }
+
+            self.accrue_interest_with(params);
+
+            let user_deposit_18 = self.convert_10_to_18_decimals(user_deposit);
+            let total_liquidity = self.total_liquidity.get_or_default();
+            let total_principal = self.total_principal_deposits.get_or_default();
+            let amount_18 = self.calculate_user_share(user_deposit_18, total_liquidity, total_principal);
+
+            // Only liquidity not lent out can leave the pool
+            let total_borrowed = self.total_borrowed.get_or_default();
+            if amount_18 > total_liquidity.saturating_sub(total_borrowed) {
+                return Err(Error::UnavailableFunds);
+            }
+
+            self.user_deposits.remove(&user);
+            self.total_liquidity.set(&total_liquidity.saturating_sub(amount_18));
+            self.total_principal_deposits.set(&total_principal.saturating_sub(user_deposit_18));
+
+            self.debit_cash(amount_18);
+            if self.env().transfer(target, U256::from(amount_18)).is_err() {
+                return Err(Error::TransactionFailed);
+            }
+
+            self.env().emit_event(DepositExported {
+                asset_id: self.asset_id,
+                user,
+                target,
+                amount: amount_18,
+            });
+
+            Ok(amount_18)
+        }
+
+        /// Top up the reserves directly with the transferred value
+        /// Lets a treasury or external backstop pre-fund the loss buffer
+        /// Transferred value is in 18 decimals (chain format)
+        #[ink(message, payable)]
+        pub fn fund_reserves(&mut self) -> Result<(), Error> {
+            let received_u256 = self.env().transferred_value();
+            if received_u256 == U256::zero() {
+                return Err(Error::ZeroAmount);
+            }
+            if received_u256 > U256::from(u128::MAX) {
+                return Err(Error::Overflow);
+            }
+            let amount: Balance = received_u256.as_u128(); // 18 decimals
+
+            self.credit_cash(amount);
+
+            let mut reserves = self.reserved_funds.get_or_default();
+            reserves = reserves.saturating_add(amount);
+            self.reserved_funds.set(&reserves);
+
+            self.env().emit_event(ReservesFunded {
+                asset_id: self.asset_id,
+                amount,
+                total_reserves: reserves,
+            });
+
+            Ok(())
+        }
+
+        /// Distribute reserves to several recipients atomically
+        /// Only callable by the Config admin. Amounts are in 18 decimals (chain format).
+        /// The total must not exceed `reserved_funds`; if any transfer fails the whole call
+        /// reverts, so there is never a partial distribution.
+        #[ink(message)]
+        pub fn distribute_reserves(&mut self, recipients: Vec<(Address, Balance)>) -> Result<(), Error> {
+            self.ensure_admin()?;
+            let params = self.accrual_params();
+            self.distribute_reserves_with(recipients, &params)
+        }
+
+        /// Internal: Body of `distribute_reserves` past the admin check, on Config parameters the
+        /// caller already read
+        fn distribute_reserves_with(
+            &mut self,
+            recipients: Vec<(Address, Balance)>,
+            params: &AccrualParams,
+        ) -> Result<(), Error> {
+            let mut total: Balance = 0;
+            for (_, amount) in recipients.iter() {
+                if *amount == 0 {
+                    return Err(Error::ZeroAmount);
+                }
+                total = total.checked_add(*amount).ok_or(Error::Overflow)?;
+            }
+
+            self.accrue_interest_with(params);
+
+            let reserves = self.reserved_funds.get_or_default();
+            if total > reserves {
+                return Err(Error::UnavailableFunds);
+            }
+            self.reserved_funds.set(&reserves.saturating_sub(total));
+
+            for (recipient, amount) in recipients.into_iter() {
+                self.debit_cash(amount);
+                if self.env().transfer(recipient, U256::from(amount)).is_err() {
+                    return Err(Error::TransactionFailed);
+                }
+                self.env().emit_event(ReservesDistributed {
+                    asset_id: self.asset_id,
+                    recipient,
+                    amount,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Reconcile the pool's accounting against its actual on-chain balance
+        /// Only callable by the Config admin. Any balance above the cash the pool's messages moved
+        /// in and out (e.g. direct transfers) is moved into the reserves. A shortfall is only
+        /// reported in the event; balances are never reduced automatically.
+        #[ink(message)]
+        pub fn reconcile(&mut self) -> Result<(), Error> {
+            self.ensure_admin()?;
+            let balance = self.native_balance()?;
+            self.apply_reconcile(balance);
+            Ok(())
+        }
+
+        /// Internal: Body of `reconcile`, against the pool's on-chain `balance` (18 decimals)
+        fn apply_reconcile(&mut self, balance: Balance) {
+            let (surplus, deficit) = self.balance_drift(balance);
+            if surplus > 0 {
+                let reserves = self.reserved_funds.get_or_default();
+                self.reserved_funds.set(&reserves.saturating_add(surplus));
+                self.credit_cash(surplus);
+            }
+
+            self.env().emit_event(Reconciled {
+                asset_id: self.asset_id,
+                surplus,
+                deficit,
+            });
+        }
+
+        /// Send native tokens transferred directly to the contract (and so not credited
+        /// to any deposit) to `to`
+        /// Only callable by the Config admin. Sweeps the balance above the accounted funds
+        /// and returns the swept amount in 18 decimals
+        #[ink(message)]
+        pub fn sweep_surplus(&mut self, to: Address) -> Result<Balance, Error> {
+            self.ensure_admin()?;
+            self.apply_sweep(to)
+        }
+
+        /// Internal: Body of `sweep_surplus`, past the admin check
+        fn apply_sweep(&mut self, to: Address) -> Result<Balance, Error> {
+            let (surplus, _deficit) = self.balance_drift(self.native_balance()?);
+            if surplus == 0 {
+                return Err(Error::NoSurplus);
+            }
+
+            if self.env().transfer(to, U256::from(surplus)).is_err() {
+                return Err(Error::TransactionFailed);
+            }
+
+            Ok(surplus)
+        }
+
+        /// Internal: The pool's on-chain native balance (18 decimals)
+        fn native_balance(&self) -> Result<Balance, Error> {
+            let balance_u256 = self.env().balance();
+            if balance_u256 > U256::from(u128::MAX) {
+                return Err(Error::Overflow);
+            }
+            Ok(balance_u256.as_u128())
+        }
+
+        /// Internal: Compare an on-chain `balance` against the cash the pool's own messages account for
+        /// Accrued interest is credited to total_liquidity before borrowers pay it, so
+        /// total_liquidity + reserved_funds runs ahead of the balance while loans are outstanding
+        /// and is not what the balance is compared to. Returns (surplus, deficit) in 18 decimals;
+        /// at most one of them is nonzero
+        fn balance_drift(&self, balance: Balance) -> (Balance, Balance) {
+            let accounted = self.cash_balance.get_or_default();
+            (balance.saturating_sub(accounted), accounted.saturating_sub(balance))
+        }
+
+        /// Internal: Record native value received by a payable message (18 decimals)
+        fn credit_cash(&mut self, amount: Balance) {
+            let cash = self.cash_balance.get_or_default();
+            self.cash_balance.set(&cash.saturating_add(amount));
+        }
+
+        /// Internal: Record native value about to be sent out (18 decimals)
+        fn debit_cash(&mut self, amount: Balance) {
+            let cash = self.cash_balance.get_or_default();
+            self.cash_balance.set(&cash.saturating_sub(amount));
+        }
+
+        /// Pay a keeper bounty out of the keeper pool
+        /// Only callable by the authorized loan manager contract
+        /// amount: in 10 decimals (storage format)
+        /// Returns the amount paid in 18 decimals; 0 if the keeper pool can't cover the bounty
+        #[ink(message)]
+        pub fn pay_keeper_bounty(&mut self, keeper: Address, amount: Balance) -> Result<Balance, Error> {
+            // Verify caller is the authorized loan manager
+            self.ensure_loan_manager()?;
+
+            let amount_18 = self.convert_10_to_18_decimals(amount);
+            let keeper_pool = self.keeper_pool.get_or_default();
+            if amount_18 == 0 || amount_18 > keeper_pool {
+                return Ok(0);
+            }
+
+            self.keeper_pool.set(&keeper_pool.saturating_sub(amount_18));
+            self.debit_cash(amount_18);
+            if self.env().transfer(keeper, U256::from(amount_18)).is_err() {
+                return Err(Error::TransactionFailed);
+            }
+
+            self.env().emit_event(KeeperBountyPaid {
+                asset_id: self.asset_id,
+                keeper,
+                amount: amount_18,
+            });
+
+            Ok(amount_18)
+        }
+
+        /// Write off borrowed funds that will never be repaid
+        /// Used when the admin force-resolves an active loan without payment
+        /// Only callable by the authorized loan manager contract
+        /// amount: in 10 decimals (storage format)
+        #[ink(message)]
+        pub fn write_off_borrowed(&mut self, amount: Balance) -> Result<(), Error> {
+            // Verify caller is the authorized loan manager
+            self.ensure_loan_manager()?;
+
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            self.accrue_interest();
+
+            let amount_18 = self.convert_10_to_18_decimals(amount);
+            let mut borrowed = self.total_borrowed.get_or_default();
+            borrowed = borrowed.saturating_sub(amount_18);
+            self.total_borrowed.set(&borrowed);
+
+            // Reserves lent out can't exceed what is still borrowed
+            let lent = self.reserves_lent.get_or_default();
+            self.reserves_lent.set(&lent.min(borrowed));
 
             Ok(())
         }
@@ -565,6 +1594,12 @@ mod lending_pool {
             // Verify caller is the authorized vouch contract
             self.ensure_vouch_contract()?;
 
+            self.apply_slash(user, amount)
+        }
+
+        /// Internal: Slash a user's position, shared by `slash_stake` and `commit_slash`
+        /// amount: in 10 decimals (storage format)
+        fn apply_slash(&mut self, user: AccountId, amount: Balance) -> Result<(), Error> {
             self.accrue_interest();
             
             let user_balance = self.user_deposits.get(&user).unwrap_or(0);
@@ -638,6 +1673,13 @@ mod lending_pool {
             // Verify caller is the authorized vouch contract
             self.ensure_vouch_contract()?;
 
+            self.apply_default_recovery(total_slashed_capital, loan_amount);
+            Ok(())
+        }
+
+        /// Internal: Return slashed capital to the pool, shared by `handle_default_recovery` and `commit_slash`
+        /// Both amounts in 10 decimals (storage format)
+        fn apply_default_recovery(&mut self, total_slashed_capital: Balance, loan_amount: Balance) {
             // Convert to 18 decimals for calculations
             let total_slashed_18 = self.convert_10_to_18_decimals(total_slashed_capital);
             let loan_amount_18 = self.convert_10_to_18_decimals(loan_amount);
@@ -692,10 +1734,96 @@ mod lending_pool {
                 reserved_funds = reserved_funds.saturating_add(deficit);
                 self.reserved_funds.set(&reserved_funds);
             }
+        }
+
+        /// Schedule a slash of a voucher's position instead of executing it immediately
+        /// Only callable by the authorized vouch contract, used when Config's slash_delay is set.
+        /// The amount stays locked as staked capital until `commit_slash` or `cancel_slash`.
+        /// amount, loan_amount: in 10 decimals (storage format); loan_amount is the part of the defaulted
+        /// loan this slash covers, passed to the default recovery on commit. Fails with
+        /// SlashAlreadyPending while another slash awaits commit, so a delay is never shortened.
+        #[ink(message)]
+        pub fn pre_slash(&mut self, user: AccountId, amount: Balance, loan_amount: Balance) -> Result<(), Error> {
+            // Verify caller is the authorized vouch contract
+            self.ensure_vouch_contract()?;
+
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            if self.pending_slashes.contains(&user) {
+                return Err(Error::SlashAlreadyPending);
+            }
+
+            let now = self.env().block_timestamp();
+            self.pending_slashes.insert(&user, &(amount, loan_amount, now));
+
+            self.env().emit_event(SlashScheduled {
+                asset_id: self.asset_id,
+                user,
+                amount,
+                loan_amount,
+            });
+
+            Ok(())
+        }
+
+        /// Execute a user's pending slash once slash_delay has passed since it was scheduled
+        /// Callable by anyone. The slashed capital goes back to the pool like any default recovery.
+        /// Returns the amount slashed in 10 decimals
+        #[ink(message)]
+        pub fn commit_slash(&mut self, user: AccountId) -> Result<Balance, Error> {
+            let (amount, loan_amount, scheduled_at) = self.pending_slashes.get(&user).ok_or(Error::NoPendingSlash)?;
+            let now = self.env().block_timestamp();
+            if now.saturating_sub(scheduled_at) < self.config.get_slash_delay() {
+                return Err(Error::SlashDelayActive);
+            }
+
+            self.pending_slashes.remove(&user);
+            self.apply_slash(user, amount)?;
+            self.apply_default_recovery(amount, loan_amount);
+
+            self.env().emit_event(SlashCommitted {
+                asset_id: self.asset_id,
+                user,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        /// Cancel a user's pending slash (e.g., a default caused by a clock error)
+        /// Only callable by the Config admin. The pending amount is released from staked capital,
+        /// so the user keeps their full deposit.
+        #[ink(message)]
+        pub fn cancel_slash(&mut self, user: AccountId) -> Result<(), Error> {
+            self.ensure_admin()?;
+
+            let (amount, _, _) = self.pending_slashes.get(&user).ok_or(Error::NoPendingSlash)?;
+            self.pending_slashes.remove(&user);
+
+            let current_staked = self.user_staked_capital.get(&user).unwrap_or(0);
+            let new_staked = current_staked.saturating_sub(amount);
+            if new_staked == 0 {
+                self.user_staked_capital.remove(&user);
+            } else {
+                self.user_staked_capital.insert(&user, &new_staked);
+            }
+
+            self.env().emit_event(SlashCancelled {
+                asset_id: self.asset_id,
+                user,
+                amount,
+            });
 
             Ok(())
         }
 
+        /// Get a user's pending slash as (amount, loan amount it covers, both in 10 decimals, time it was scheduled)
+        #[ink(message)]
+        pub fn get_pending_slash(&self, user: AccountId) -> Option<(Balance, Balance, Timestamp)> {
+            self.pending_slashes.get(&user)
+        }
+
         /// Increase staked capital for a user (only callable by vouch contract)
         /// amount: in 10 decimals (storage format)
         #[ink(message)]
@@ -752,6 +1880,15 @@ mod lending_pool {
             self.user_staked_capital.get(&user).unwrap_or(0)
         }
 
+        /// Get the part of a user's deposit that is not pledged as vouch stake
+        /// Returns amount in 10 decimals (storage format)
+        #[ink(message)]
+        pub fn get_available_deposit(&self, user: AccountId) -> Balance {
+            let user_deposit = self.user_deposits.get(&user).unwrap_or(0);
+            let user_staked = self.user_staked_capital.get(&user).unwrap_or(0);
+            user_deposit.saturating_sub(user_staked)
+        }
+
         /// Get comprehensive user information
         /// Returns all user data including deposit, staked capital, available balance, and yield
         #[ink(message)]
@@ -791,4 +1928,741 @@ mod lending_pool {
             }
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const ADMIN: [u8; 32] = [0x01; 32];
+        const USER: [u8; 32] = [0x02; 32];
+
+        fn address_of(account: [u8; 32]) -> Address {
+            AccountIdMapper::to_address(&account)
+        }
+
+        fn vouch_contract() -> Address {
+            Address::from([0xA1; 20])
+        }
+
+        fn new_pool() -> LendingPool {
+            ink::env::test::set_caller(address_of(ADMIN));
+            let mut pool = LendingPool::new(Address::from([0x10; 20]), 0);
+            assert_eq!(pool.set_vouch_contract(vouch_contract()), Ok(()));
+            pool
+        }
+
+        /// Config's default rate curve: 10% base, 80% optimal, +4% / +75% slopes, 100% max
+        fn default_rate_params() -> RateParams {
+            RateParams {
+                base_interest_rate: 10_000_000_000,
+                optimal_utilization: 80_000_000_000,
+                slope1: 4_000_000_000,
+                slope2: 75_000_000_000,
+                max_rate: 100_000_000_000,
+                rate_scale: 1_000_000_000,
+                smoothing_factor: 0,
+                reserve_lending_percent: 0,
+            }
+        }
+
+        #[ink::test]
+        fn rate_curve_matches_hand_computed_points() {
+            let pool = new_pool();
+            let params = default_rate_params();
+            let percent = |p: u64| p * 1_000_000_000;
+
+            // 0%: base
+            assert_eq!(pool.calculate_rate(&params, 0), percent(10));
+            // 40%: base + 40/80 * 4% = 12%
+            assert_eq!(pool.calculate_rate(&params, percent(40)), percent(12));
+            // 80%, the kink: base + slope1 = 14%
+            assert_eq!(pool.calculate_rate(&params, percent(80)), percent(14));
+            // 90%: 14% + 10/20 * 75% = 51.5%
+            assert_eq!(pool.calculate_rate(&params, percent(90)), 51_500_000_000);
+            // 100%: 14% + 75% = 89%; anything above is capped at 100% utilization
+            assert_eq!(pool.calculate_rate(&params, percent(100)), percent(89));
+            assert_eq!(pool.calculate_rate(&params, percent(150)), percent(89));
+        }
+
+        #[ink::test]
+        fn reconcile_moves_a_surplus_into_reserves() {
+            let mut pool = new_pool();
+            pool.credit_cash(1_000);
+            pool.reserved_funds.set(&(50 as Balance));
+
+            // 300 arrived by direct transfer, outside any pool message
+            pool.apply_reconcile(1_300);
+            assert_eq!(pool.reserved_funds.get_or_default(), 350);
+            assert_eq!(pool.cash_balance.get_or_default(), 1_300);
+            // Once absorbed the surplus is not counted again
+            assert_eq!(pool.balance_drift(1_300), (0, 0));
+        }
+
+        #[ink::test]
+        fn reconcile_only_reports_a_deficit() {
+            let mut pool = new_pool();
+            pool.credit_cash(1_000);
+            pool.reserved_funds.set(&(50 as Balance));
+            pool.total_liquidity.set(&(950 as Balance));
+
+            assert_eq!(pool.balance_drift(800), (0, 200));
+            pool.apply_reconcile(800);
+            // Nothing is written down automatically
+            assert_eq!(pool.reserved_funds.get_or_default(), 50);
+            assert_eq!(pool.total_liquidity.get_or_default(), 950);
+            assert_eq!(pool.cash_balance.get_or_default(), 1_000);
+        }
+
+        /// Default rate curve with Config's default reserve tiers and keeper fee
+        fn default_accrual_params() -> AccrualParams {
+            AccrualParams {
+                rate: default_rate_params(),
+                min_accrual_utilization: 0,
+                reserve_factor_tiers: (20, 30, 80_000_000_000),
+                keeper_fee_percent: 5,
+            }
+        }
+
+        fn set_time(ts: Timestamp) {
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(ts);
+        }
+
+        #[ink::test]
+        fn principal_exchange_rate_is_one_to_one_when_empty_or_unchanged() {
+            let mut pool = new_pool();
+            assert_eq!(pool.principal_exchange_rate(), LendingPool::EXCHANGE_RATE_SCALE);
+
+            pool.total_principal_deposits.set(&(1_000 as Balance));
+            pool.total_liquidity.set(&(1_000 as Balance));
+            assert_eq!(pool.principal_exchange_rate(), LendingPool::EXCHANGE_RATE_SCALE);
+        }
+
+        #[ink::test]
+        fn principal_exchange_rate_rises_with_accrued_interest() {
+            set_time(0);
+            let mut pool = new_pool();
+            // 1_000 deposited, 500 of it out on loan
+            pool.total_principal_deposits.set(&(1_000_000_000_000 as Balance));
+            pool.total_liquidity.set(&(500_000_000_000 as Balance));
+            pool.total_borrowed.set(&(500_000_000_000 as Balance));
+            let before = pool.principal_exchange_rate();
+            assert_eq!(before, LendingPool::EXCHANGE_RATE_SCALE / 2);
+
+            set_time(LendingPool::YEAR_MS as Timestamp);
+            pool.accrue_interest_with(&default_accrual_params());
+            let liquidity = pool.total_liquidity.get_or_default();
+            assert!(liquidity > 500_000_000_000);
+            assert!(pool.principal_exchange_rate() > before);
+            assert_eq!(
+                pool.principal_exchange_rate(),
+                liquidity * LendingPool::EXCHANGE_RATE_SCALE / 1_000_000_000_000
+            );
+        }
+
+        /// Sets the pool contract's own native balance, which outgoing transfers are paid from
+        fn fund_pool(balance: Balance) {
+            ink::env::test::set_account_balance(
+                ink::env::test::callee::<ink::env::DefaultEnvironment>(),
+                U256::from(balance),
+            );
+        }
+
+        /// Seeds a 1_000 deposit (10 decimals) by USER as the pool's only liquidity
+        fn seed_deposit(pool: &mut LendingPool) -> AccountId {
+            let user = AccountId::from(USER);
+            pool.user_deposits.insert(&user, &(1_000 as Balance));
+            pool.total_principal_deposits.set(&(100_000_000_000 as Balance));
+            pool.total_liquidity.set(&(100_000_000_000 as Balance));
+            pool.credit_cash(100_000_000_000);
+            fund_pool(100_000_000_000);
+            user
+        }
+
+        #[ink::test]
+        fn withdraw_cannot_reach_staked_capital() {
+            let mut pool = new_pool();
+            let user = seed_deposit(&mut pool);
+            pool.user_staked_capital.insert(&user, &(600 as Balance));
+            let params = default_accrual_params();
+
+            assert_eq!(pool.withdraw_with(Some(401), user, 0, &params), Err(Error::FundsEncumbered));
+            assert_eq!(pool.withdraw_with(Some(400), user, 0, &params), Ok(400));
+            assert_eq!(pool.get_user_deposit(user), 600);
+            assert_eq!(pool.withdraw_with(Some(1), user, 0, &params), Err(Error::FundsEncumbered));
+        }
+
+        #[ink::test]
+        fn withdraw_all_takes_only_the_unencumbered_deposit() {
+            let mut pool = new_pool();
+            let user = seed_deposit(&mut pool);
+            pool.user_staked_capital.insert(&user, &(600 as Balance));
+            let params = default_accrual_params();
+
+            assert_eq!(pool.withdraw_with(None, user, 0, &params), Ok(400));
+            assert_eq!(pool.get_user_deposit(user), 600);
+            assert_eq!(pool.get_available_deposit(user), 0);
+            // Only the staked part is left
+            assert_eq!(pool.withdraw_with(None, user, 0, &params), Err(Error::ZeroAmount));
+        }
+
+        #[ink::test]
+        fn rate_scale_drives_the_rate_curve_and_utilization() {
+            let mut pool = new_pool();
+            // The default curve restated on a 1e6 scale: 10% base, 80% optimal, +4% / +75%
+            let params = RateParams {
+                base_interest_rate: 10_000_000,
+                optimal_utilization: 80_000_000,
+                slope1: 4_000_000,
+                slope2: 75_000_000,
+                max_rate: 100_000_000,
+                rate_scale: 1_000_000,
+                ..default_rate_params()
+            };
+            assert_eq!(pool.calculate_rate(&params, 40_000_000), 12_000_000);
+            assert_eq!(pool.calculate_rate(&params, 90_000_000), 51_500_000);
+            // 100% is 100 * rate_scale, so the cap follows the scale
+            assert_eq!(pool.calculate_rate(&params, 100_000_000), 89_000_000);
+            assert_eq!(pool.calculate_rate(&params, 100_000_000_000), 89_000_000);
+
+            pool.total_liquidity.set(&(1_000 as Balance));
+            pool.total_borrowed.set(&(500 as Balance));
+            assert_eq!(pool.utilization_percent(&params), 50_000_000);
+            assert_eq!(pool.utilization_percent(&default_rate_params()), 50_000_000_000);
+        }
+
+        #[ink::test]
+        fn rate_per_second_precision_is_the_rate_scale() {
+            let seconds_per_year = LendingPool::YEAR_MS / 1000;
+            for rate_scale in [1_000_000u64, 1_000_000_000] {
+                let annual = 10 * rate_scale; // 10%
+                let per_second = LendingPool::rate_per_second(annual, rate_scale);
+                let recovered = per_second * seconds_per_year / rate_scale as u128;
+                // Truncation loses less than one rate unit
+                assert!(annual as u128 - recovered <= 1, "rate_scale {rate_scale}: {recovered}");
+            }
+        }
+
+        #[ink::test]
+        fn rate_curve_is_capped_at_max_rate() {
+            let pool = new_pool();
+            let params = RateParams { max_rate: 50_000_000_000, ..default_rate_params() };
+            assert_eq!(pool.calculate_rate(&params, 100_000_000_000), 50_000_000_000);
+        }
+
+        #[ink::test]
+        fn staked_capital_cannot_exceed_the_deposit() {
+            let mut pool = new_pool();
+            let user = AccountId::from(USER);
+            pool.user_deposits.insert(&user, &(100 as Balance));
+
+            ink::env::test::set_caller(vouch_contract());
+            assert_eq!(pool.increase_staked_capital(user, 60), Ok(()));
+            assert_eq!(pool.increase_staked_capital(user, 41), Err(Error::UnavailableFunds));
+            assert_eq!(pool.get_user_staked_capital(user), 60);
+            assert_eq!(pool.get_available_deposit(user), 40);
+
+            assert_eq!(pool.decrease_staked_capital(user, 61), Err(Error::UnavailableFunds));
+            assert_eq!(pool.decrease_staked_capital(user, 60), Ok(()));
+            assert_eq!(pool.get_available_deposit(user), 100);
+        }
+
+        #[ink::test]
+        fn staked_capital_is_only_moved_by_the_vouch_contract() {
+            let mut pool = new_pool();
+            let user = AccountId::from(USER);
+            pool.user_deposits.insert(&user, &(100 as Balance));
+
+            ink::env::test::set_caller(address_of(USER));
+            assert_eq!(pool.increase_staked_capital(user, 10), Err(Error::Unauthorized));
+            assert_eq!(pool.decrease_staked_capital(user, 10), Err(Error::Unauthorized));
+            assert_eq!(pool.get_user_staked_capital(user), 0);
+        }
+
+        #[ink::test]
+        fn extension_fee_is_only_accepted_from_the_loan_manager() {
+            let mut pool = new_pool();
+            let loan_manager = Address::from([0xC3; 20]);
+            assert_eq!(pool.set_loan_manager(loan_manager), Ok(()));
+
+            ink::env::test::set_caller(vouch_contract());
+            assert_eq!(pool.receive_extension_fee(1), Err(Error::Unauthorized));
+            // The loan manager gets past the role check; nothing was transferred
+            ink::env::test::set_caller(loan_manager);
+            assert_eq!(pool.receive_extension_fee(1), Err(Error::ZeroAmount));
+        }
+
+        #[ink::test]
+        fn write_off_is_only_accepted_from_the_loan_manager() {
+            let mut pool = new_pool();
+            let loan_manager = Address::from([0xC3; 20]);
+            assert_eq!(pool.set_loan_manager(loan_manager), Ok(()));
+
+            ink::env::test::set_caller(address_of(ADMIN));
+            assert_eq!(pool.write_off_borrowed(1), Err(Error::Unauthorized));
+            ink::env::test::set_caller(loan_manager);
+            assert_eq!(pool.write_off_borrowed(0), Err(Error::ZeroAmount));
+        }
+
+        #[ink::test]
+        fn keeper_bounty_is_skipped_when_the_keeper_pool_cannot_cover_it() {
+            let mut pool = new_pool();
+            let loan_manager = Address::from([0xC3; 20]);
+            assert_eq!(pool.set_loan_manager(loan_manager), Ok(()));
+            let keeper = address_of(USER);
+
+            ink::env::test::set_caller(keeper);
+            assert_eq!(pool.pay_keeper_bounty(keeper, 1), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller(loan_manager);
+            assert_eq!(pool.pay_keeper_bounty(keeper, 1), Ok(0));
+            assert_eq!(pool.pay_keeper_bounty(keeper, 0), Ok(0));
+            assert_eq!(pool.get_keeper_pool(), 0);
+        }
+
+        #[ink::test]
+        fn pre_slash_schedules_one_slash_at_a_time() {
+            let mut pool = new_pool();
+            let user = AccountId::from(USER);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5_000);
+
+            ink::env::test::set_caller(address_of(USER));
+            assert_eq!(pool.pre_slash(user, 10, 50), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller(vouch_contract());
+            assert_eq!(pool.pre_slash(user, 0, 50), Err(Error::ZeroAmount));
+            assert_eq!(pool.pre_slash(user, 10, 50), Ok(()));
+            assert_eq!(pool.get_pending_slash(user), Some((10, 50, 5_000)));
+
+            // A second slash would restart the delay of the first one
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(9_000);
+            assert_eq!(pool.pre_slash(user, 20, 80), Err(Error::SlashAlreadyPending));
+            assert_eq!(pool.get_pending_slash(user), Some((10, 50, 5_000)));
+        }
+
+        #[ink::test]
+        fn commit_slash_needs_a_pending_slash() {
+            let mut pool = new_pool();
+            assert_eq!(pool.commit_slash(AccountId::from(USER)), Err(Error::NoPendingSlash));
+        }
+
+        #[ink::test]
+        fn setup_addresses_are_registered_once_with_their_role() {
+            let mut pool = new_pool();
+            assert!(pool.is_authorized(vouch_contract()));
+            assert_eq!(pool.set_vouch_contract(Address::from([0xB2; 20])), Err(Error::Unauthorized));
+            assert!(!pool.is_authorized(Address::from([0xB2; 20])));
+
+            // The vouch role does not grant loan manager messages
+            ink::env::test::set_caller(vouch_contract());
+            assert_eq!(pool.write_off_borrowed(1), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn guarded_messages_reject_reentry() {
+            let mut pool = new_pool();
+            pool.entered.set(&true);
+            assert_eq!(pool.withdraw(1, AccountId::from(USER)), Err(Error::Reentrancy));
+            assert_eq!(pool.receive_repayment(1), Err(Error::Reentrancy));
+            assert_eq!(pool.disburse(1, AccountId::from(USER)), Err(Error::Reentrancy));
+        }
+
+        #[ink::test]
+        fn reentrancy_guard_is_released_after_an_error() {
+            let mut pool = new_pool();
+            // Fails inside the guard on the zero amount
+            assert_eq!(pool.withdraw(0, AccountId::from(USER)), Err(Error::ZeroAmount));
+            assert!(!pool.entered.get_or_default());
+        }
+
+        /// Pool with `borrowed` out of 1_000e9 liquidity, last accrued at 0 and the clock a year on
+        fn pool_with_borrowed(borrowed: Balance) -> LendingPool {
+            set_time(0);
+            let mut pool = new_pool();
+            pool.total_liquidity.set(&(1_000_000_000_000 as Balance));
+            pool.total_borrowed.set(&borrowed);
+            pool.last_update.set(&0);
+            set_time(LendingPool::YEAR_MS as Timestamp);
+            pool
+        }
+
+        #[ink::test]
+        fn reserve_factor_switches_above_the_utilization_threshold() {
+            let params = default_accrual_params();
+
+            // Exactly at the 80% threshold the low factor (20%) still applies
+            let pool = pool_with_borrowed(800_000_000_000);
+            let (interest, reserve) = pool.project_interest(&params, LendingPool::YEAR_MS as Timestamp);
+            assert!(interest > 0);
+            assert_eq!(reserve, (interest * 20).div_ceil(100));
+
+            // Above it the high factor (30%) does
+            let pool = pool_with_borrowed(900_000_000_000);
+            let (interest, reserve) = pool.project_interest(&params, LendingPool::YEAR_MS as Timestamp);
+            assert!(interest > 0);
+            assert_eq!(reserve, (interest * 30).div_ceil(100));
+        }
+
+
+        #[ink::test]
+        fn smoothed_rate_moves_toward_the_spot_rate_across_accruals() {
+            let mut pool = pool_with_borrowed(0);
+            let mut params = default_accrual_params();
+            params.rate.smoothing_factor = 50;
+            // Nothing borrowed, so the spot rate is the 10% base rate
+            assert_eq!(pool.current_rate(&params.rate), 10_000_000_000);
+            pool.smoothed_rate.set(&20_000_000_000);
+            assert_eq!(pool.accrual_rate(&params.rate), 20_000_000_000);
+
+            // Each accrual closes half the remaining gap
+            for (step, expected) in [15_000_000_000u64, 12_500_000_000, 11_250_000_000].into_iter().enumerate() {
+                set_time(LendingPool::YEAR_MS as Timestamp + step as Timestamp + 1);
+                pool.accrue_interest_with(&params);
+                assert_eq!(pool.smoothed_rate.get_or_default(), expected);
+                assert_eq!(pool.accrual_rate(&params.rate), expected);
+            }
+
+            // Turning smoothing off clears the stored rate and accrual follows the spot rate again
+            params.rate.smoothing_factor = 0;
+            pool.accrue_interest_with(&params);
+            assert_eq!(pool.smoothed_rate.get_or_default(), 0);
+            assert_eq!(pool.accrual_rate(&params.rate), 10_000_000_000);
+        }
+
+
+        #[ink::test]
+        fn a_year_of_accrual_charges_the_annual_rate() {
+            // 50% utilization: 10% + 4% * 50/80 = 12.5%
+            let pool = pool_with_borrowed(500_000_000_000);
+            let params = default_accrual_params();
+            assert_eq!(pool.accrual_rate(&params.rate), 12_500_000_000);
+
+            let (interest, _) = pool.project_interest(&params, LendingPool::YEAR_MS as Timestamp);
+            assert_eq!(interest, 62_500_000_000);
+            let (half, _) = pool.project_interest(&params, LendingPool::YEAR_MS as Timestamp / 2);
+            assert_eq!(half, 31_250_000_000);
+        }
+
+        #[ink::test]
+        fn rate_per_second_reproduces_a_second_of_accrual() {
+            let params = default_accrual_params();
+            let rate_scale = params.rate.rate_scale as u128;
+            for borrowed in [500_000_000_000 as Balance, 500_000_000_000_000_000_000] {
+                let mut pool = pool_with_borrowed(borrowed);
+                pool.total_liquidity.set(&(borrowed * 2));
+                let rate = pool.accrual_rate(&params.rate);
+                let per_second = LendingPool::rate_per_second(rate, params.rate.rate_scale);
+
+                let (interest, _) = pool.project_interest(&params, 1_000);
+                let projected = borrowed * per_second / (100 * rate_scale * rate_scale);
+                assert!(interest.abs_diff(projected) <= 1, "{interest} vs {projected}");
+            }
+        }
+
+
+        #[ink::test]
+        fn pending_interest_projects_what_accrual_applies() {
+            let mut pool = pool_with_borrowed(500_000_000_000);
+            let params = default_accrual_params();
+            let now = LendingPool::YEAR_MS as Timestamp;
+
+            let (interest, reserve) = pool.project_interest(&params, now);
+            assert!(interest > 0 && reserve > 0 && reserve < interest);
+            // Projecting is read-only: a second projection sees the same state
+            assert_eq!(pool.project_interest(&params, now), (interest, reserve));
+            assert_eq!(pool.last_update.get_or_default(), 0);
+
+            pool.accrue_interest_with(&params);
+            assert_eq!(pool.get_cumulative_interest(), interest);
+            assert_eq!(pool.reserved_funds.get_or_default(), reserve);
+            // Nothing is left pending once accrued
+            assert_eq!(pool.project_interest(&params, now), (0, 0));
+        }
+
+
+        fn no_disburse_limits() -> DisburseLimits {
+            DisburseLimits { reserve_lending_percent: 0, min_liquidity_buffer: 0, global_borrow_cap: 0 }
+        }
+
+        #[ink::test]
+        fn disburse_checks_report_each_failure_reason() {
+            let mut pool = new_pool();
+            pool.total_liquidity.set(&(1_000_000_000_000 as Balance));
+
+            assert_eq!(pool.check_disburse(100_000_000_000, &no_disburse_limits()), Ok(()));
+            assert_eq!(
+                pool.check_disburse(1_000_000_000_001, &no_disburse_limits()),
+                Err(Error::UnavailableFunds)
+            );
+
+            // A 1_000 (10 decimals) buffer leaves only 900e9 lendable
+            let buffered = DisburseLimits { min_liquidity_buffer: 1_000, ..no_disburse_limits() };
+            assert_eq!(pool.check_disburse(950_000_000_000, &buffered), Err(Error::BufferViolation));
+
+            // A 5_000 (10 decimals) cap on total borrowed
+            let capped = DisburseLimits { global_borrow_cap: 5_000, ..no_disburse_limits() };
+            assert_eq!(pool.check_disburse(600_000_000_000, &capped), Err(Error::GlobalCapExceeded));
+
+            // Lendable reserves top up liquidity: half of 200e9 reserves
+            pool.reserved_funds.set(&(200_000_000_000 as Balance));
+            let lending = DisburseLimits { reserve_lending_percent: 50, ..no_disburse_limits() };
+            assert_eq!(pool.check_disburse(1_100_000_000_000, &lending), Ok(()));
+            assert_eq!(pool.check_disburse(1_100_000_000_001, &lending), Err(Error::UnavailableFunds));
+        }
+
+
+        #[ink::test]
+        fn global_borrow_cap_allows_borrowing_up_to_the_cap() {
+            let mut pool = new_pool();
+            pool.total_liquidity.set(&(1_000_000_000_000 as Balance));
+            pool.total_borrowed.set(&(100_000_000_000 as Balance));
+            // 5_000 (10 decimals) = 500e9, 100e9 of it already borrowed
+            let capped = DisburseLimits { global_borrow_cap: 5_000, ..no_disburse_limits() };
+
+            assert_eq!(pool.check_disburse(400_000_000_000, &capped), Ok(()));
+            assert_eq!(pool.check_disburse(400_000_000_001, &capped), Err(Error::GlobalCapExceeded));
+            // 0 is unlimited
+            assert_eq!(pool.check_disburse(400_000_000_001, &no_disburse_limits()), Ok(()));
+        }
+
+
+        #[ink::test]
+        fn liquidity_buffer_stays_out_of_reach_of_borrowers() {
+            let mut pool = new_pool();
+            pool.total_liquidity.set(&(1_000_000_000_000 as Balance));
+            // 1_000 (10 decimals) = 100e9 held back for withdrawals
+            let buffered = DisburseLimits { min_liquidity_buffer: 1_000, ..no_disburse_limits() };
+
+            // Lending down to exactly the buffer is allowed, one unit further is not
+            assert_eq!(pool.check_disburse(900_000_000_000, &buffered), Ok(()));
+            assert_eq!(pool.check_disburse(900_000_000_001, &buffered), Err(Error::BufferViolation));
+            // Running past all liquidity reports the shortfall, not the buffer
+            assert_eq!(pool.check_disburse(1_000_000_000_001, &buffered), Err(Error::UnavailableFunds));
+        }
+
+
+        #[ink::test]
+        fn sweep_sends_only_the_unaccounted_balance() {
+            let mut pool = new_pool();
+            seed_deposit(&mut pool);
+            let to = Address::from([0xC1; 20]);
+
+            // Balance matches the cash ledger: nothing to sweep
+            assert_eq!(pool.apply_sweep(to), Err(Error::NoSurplus));
+
+            // 500 sent straight to the contract is swept, and the accounted cash stays put
+            fund_pool(100_000_000_500);
+            assert_eq!(pool.apply_sweep(to), Ok(500));
+            assert_eq!(pool.native_balance(), Ok(100_000_000_000));
+            assert_eq!(pool.cash_balance.get_or_default(), 100_000_000_000);
+            assert_eq!(pool.apply_sweep(to), Err(Error::NoSurplus));
+        }
+
+
+        #[ink::test]
+        fn cumulative_interest_adds_up_every_accrual_gross() {
+            let mut pool = pool_with_borrowed(500_000_000_000);
+            let params = default_accrual_params();
+            assert_eq!(pool.get_cumulative_interest(), 0);
+
+            let year = LendingPool::YEAR_MS as Timestamp;
+            let (first, _) = pool.project_interest(&params, year);
+            pool.accrue_interest_with(&params);
+            assert_eq!(pool.get_cumulative_interest(), 62_500_000_000);
+
+            set_time(year + year / 2);
+            let (second, _) = pool.project_interest(&params, year + year / 2);
+            pool.accrue_interest_with(&params);
+            assert!(second > 0);
+            assert_eq!(pool.get_cumulative_interest(), first + second);
+
+            // Gross of the reserve and keeper cuts, so it outgrows what suppliers were credited
+            let credited = pool.total_liquidity.get_or_default() - 1_000_000_000_000;
+            assert!(credited < pool.get_cumulative_interest());
+            assert_eq!(
+                credited + pool.reserved_funds.get_or_default() + pool.keeper_pool.get_or_default(),
+                pool.get_cumulative_interest()
+            );
+        }
+
+
+        #[ink::test]
+        fn withdraw_cooldown_holds_the_deposit_while_it_earns() {
+            set_time(0);
+            let mut pool = new_pool();
+            let user = seed_deposit(&mut pool);
+            pool.total_borrowed.set(&(50_000_000_000 as Balance));
+            pool.last_deposit_time.insert(&user, &0);
+            let params = default_accrual_params();
+            let day: Timestamp = 86_400_000;
+
+            // Right after depositing the cooldown blocks the withdrawal
+            set_time(day / 2);
+            assert_eq!(pool.withdraw_with(Some(100), user, day, &params), Err(Error::WithdrawCooldownActive));
+            assert_eq!(pool.get_cumulative_interest(), 0);
+
+            // Once it has passed the withdrawal goes through, after the deposit earned a day's interest
+            set_time(day);
+            let (earned, _) = pool.project_interest(&params, day);
+            assert!(earned > 0);
+            assert_eq!(pool.withdraw_with(Some(100), user, day, &params), Ok(100));
+            assert_eq!(pool.get_cumulative_interest(), earned);
+            // Interest raised the share price, so 100 redeems less than 100 of principal
+            assert!(pool.user_deposits.get(&user).unwrap() > 900);
+        }
+
+
+        #[ink::test]
+        fn lent_reserves_earn_their_share_back_into_reserves() {
+            let mut pool = pool_with_borrowed(500_000_000_000);
+            pool.reserved_funds.set(&(200_000_000_000 as Balance));
+            let mut params = default_accrual_params();
+            let year = LendingPool::YEAR_MS as Timestamp;
+
+            // Feature off: reserves can't be lent and only take the reserve factor
+            assert_eq!(
+                pool.check_disburse(500_000_000_001, &no_disburse_limits()),
+                Err(Error::UnavailableFunds)
+            );
+            let (interest, off_reserve) = pool.project_interest(&params, year);
+            assert_eq!(off_reserve, (interest * 20).div_ceil(100));
+
+            // Feature on: half the reserves are lendable and a fifth of the borrowing came from them
+            params.rate.reserve_lending_percent = 50;
+            let lending = DisburseLimits { reserve_lending_percent: 50, ..no_disburse_limits() };
+            assert_eq!(pool.check_disburse(600_000_000_000, &lending), Ok(()));
+            pool.reserved_funds.set(&(100_000_000_000 as Balance));
+            pool.reserves_lent.set(&(100_000_000_000 as Balance));
+
+            let (interest, on_reserve) = pool.project_interest(&params, year);
+            let earned = interest / 5;
+            assert_eq!(on_reserve, earned + ((interest - earned) * 20).div_ceil(100));
+            assert!(on_reserve > off_reserve);
+
+            pool.accrue_interest_with(&params);
+            assert_eq!(pool.reserved_funds.get_or_default(), 100_000_000_000 + on_reserve);
+        }
+
+
+        #[ink::test]
+        fn no_interest_accrues_below_min_accrual_utilization() {
+            // 5% utilization against a 10% floor
+            let mut pool = pool_with_borrowed(50_000_000_000);
+            let mut params = default_accrual_params();
+            params.min_accrual_utilization = 10_000_000_000;
+            let year = LendingPool::YEAR_MS as Timestamp;
+
+            assert_eq!(pool.project_interest(&params, year), (0, 0));
+            pool.accrue_interest_with(&params);
+            assert_eq!(pool.get_cumulative_interest(), 0);
+            assert_eq!(pool.total_liquidity.get_or_default(), 1_000_000_000_000);
+            // The skipped period is not charged later either
+            assert_eq!(pool.last_update.get_or_default(), year);
+
+            // At the floor interest accrues again
+            pool.total_borrowed.set(&(100_000_000_000 as Balance));
+            set_time(2 * year);
+            let (interest, _) = pool.project_interest(&params, 2 * year);
+            assert!(interest > 0);
+            pool.accrue_interest_with(&params);
+            assert_eq!(pool.get_cumulative_interest(), interest);
+        }
+
+
+        #[ink::test]
+        fn fund_reserves_books_the_transferred_value() {
+            let mut pool = new_pool();
+            pool.reserved_funds.set(&(1_000 as Balance));
+
+            assert_eq!(pool.fund_reserves(), Err(Error::ZeroAmount));
+
+            ink::env::test::set_value_transferred(U256::from(500));
+            assert_eq!(pool.fund_reserves(), Ok(()));
+            assert_eq!(pool.reserved_funds.get_or_default(), 1_500);
+            // Counted as the pool's own cash, so reconcile won't treat it as a surplus
+            assert_eq!(pool.cash_balance.get_or_default(), 500);
+            // Reserves sit outside supplier liquidity
+            assert_eq!(pool.total_liquidity.get_or_default(), 0);
+        }
+
+
+        #[ink::test]
+        fn distribute_reserves_pays_every_recipient_or_none() {
+            let mut pool = new_pool();
+            pool.reserved_funds.set(&(1_000 as Balance));
+            pool.credit_cash(1_000);
+            fund_pool(1_000);
+            let params = default_accrual_params();
+            let (a, b) = (Address::from([0xD1; 20]), Address::from([0xD2; 20]));
+
+            // More than the reserves, or a zero share, moves nothing
+            assert_eq!(
+                pool.distribute_reserves_with(vec![(a, 600), (b, 401)], &params),
+                Err(Error::UnavailableFunds)
+            );
+            assert_eq!(pool.distribute_reserves_with(vec![(a, 600), (b, 0)], &params), Err(Error::ZeroAmount));
+            assert_eq!(pool.reserved_funds.get_or_default(), 1_000);
+
+            assert_eq!(pool.distribute_reserves_with(vec![(a, 600), (b, 400)], &params), Ok(()));
+            assert_eq!(pool.reserved_funds.get_or_default(), 0);
+            assert_eq!(pool.cash_balance.get_or_default(), 0);
+            assert_eq!(pool.native_balance(), Ok(0));
+        }
+
+
+        #[ink::test]
+        fn export_deposit_moves_the_whole_share_to_the_target() {
+            let mut pool = new_pool();
+            let user = seed_deposit(&mut pool);
+            let params = default_accrual_params();
+
+            // No migration target set yet
+            assert_eq!(pool.export_deposit_with(user, &params), Err(Error::Unauthorized));
+            pool.migration_target.set(&Some(Address::from([0xE1; 20])));
+
+            pool.user_staked_capital.insert(&user, &(1 as Balance));
+            assert_eq!(pool.export_deposit_with(user, &params), Err(Error::FundsEncumbered));
+            pool.user_staked_capital.insert(&user, &0);
+
+            // Interest credited to liquidity goes along with the principal
+            pool.total_liquidity.set(&(100_000_000_500 as Balance));
+            fund_pool(100_000_000_500);
+            assert_eq!(pool.export_deposit_with(user, &params), Ok(100_000_000_500));
+            assert_eq!(pool.user_deposits.get(&user), None);
+            assert_eq!(pool.total_liquidity.get_or_default(), 0);
+            assert_eq!(pool.total_principal_deposits.get_or_default(), 0);
+            assert_eq!(pool.export_deposit_with(user, &params), Err(Error::ZeroAmount));
+        }
+
+
+        #[ink::test]
+        fn rebalance_steps_the_base_rate_within_its_bounds() {
+            let params = default_accrual_params();
+            let step = 5_000_000_000;
+
+            // 90% utilization is above the 80% optimum: the base rate climbs one step per call
+            let mut pool = pool_with_borrowed(900_000_000_000);
+            // Already accrued up to now, so utilization holds still across the calls
+            pool.last_update.set(&(LendingPool::YEAR_MS as Timestamp));
+            pool.apply_rebalance(&params, step);
+            assert_eq!(pool.get_base_rate_adjustment(), 5_000_000_000);
+            assert_eq!(pool.effective_base_rate(&params.rate), 15_000_000_000);
+            // ...until it reaches max_rate (100%), however many steps that would take
+            for _ in 0..30 {
+                pool.apply_rebalance(&params, step);
+            }
+            assert_eq!(pool.get_base_rate_adjustment(), 90_000_000_000);
+            assert_eq!(pool.effective_base_rate(&params.rate), 100_000_000_000);
+
+            // 10% utilization is below it: the base rate falls, but never below zero
+            let mut pool = pool_with_borrowed(100_000_000_000);
+            // Already accrued up to now, so utilization holds still across the calls
+            pool.last_update.set(&(LendingPool::YEAR_MS as Timestamp));
+            pool.apply_rebalance(&params, step);
+            assert_eq!(pool.effective_base_rate(&params.rate), 5_000_000_000);
+            pool.apply_rebalance(&params, step);
+            pool.apply_rebalance(&params, step);
+            assert_eq!(pool.get_base_rate_adjustment(), -10_000_000_000);
+            assert_eq!(pool.effective_base_rate(&params.rate), 0);
+        }
+    }
 }
\ No newline at end of file
@@ -4,12 +4,13 @@
 
 #[ink::contract]
 mod loan_manager {
-    use config::ConfigRef;
+    use config::{mul_div, ConfigRef, Rounding};
     use reputation::ReputationRef;
-    use lending_pool::LendingPoolRef;
+    use lending_pool::{LendingPoolError, LendingPoolRef};
     use vouch::VouchRef;
     use ink::storage::Mapping;
     use ink::U256;
+    use ink::primitives::AccountIdMapper;
     use ink::prelude::vec::Vec;
 
     /// Struct for loan information
@@ -24,6 +25,66 @@ mod loan_manager {
         borrower: AccountId,  // [u8; 32]
         status: LoanStatus,
         total_repayment_amount: Balance, // Fixed repayment amount calculated at loan creation
+        extensions: u32, // Number of times the term has been extended
+        num_installments: u32, // Number of scheduled payments (1 = single payment at term)
+        installments_paid: u32, // Number of installments paid so far
+        repaid_amount: Balance, // Total amount repaid so far (10 decimals)
+        vouchers: Vec<AccountId>, // Vouchers backing this loan, recorded at disbursement
+    }
+
+    /// Aggregated underwriting view of a borrower across Reputation, Vouch and LoanManager
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct BorrowerProfile {
+        /// Borrower's current stars
+        pub stars: u32,
+        /// Number of active vouch relationships backing the borrower
+        pub active_vouches: u32,
+        /// Total capital staked by the borrower's active vouchers (in 10 decimals)
+        pub vouched_capital: Balance,
+        /// Number of the borrower's outstanding loans (Active or PendingDefault)
+        pub active_loans: u32,
+        /// Principal not yet repaid across the borrower's outstanding loans (in 10 decimals)
+        pub outstanding_principal: Balance,
+    }
+
+    /// Config parameters the LoanManager contract reads, as returned by `dependent_config`
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct DependentConfig {
+        pub admin: AccountId,
+        pub rate_scale: u64,
+        pub max_rate: u64,
+        pub loan_max_rate: u64,
+        pub amount_scale: Balance,
+        pub loan_tier_scaling_factor: Balance,
+        pub loan_tier1_max_scaled_amount: Balance,
+        pub loan_tier2_max_scaled_amount: Balance,
+        pub loan_tier1_max_amount: Balance,
+        pub loan_tier2_max_amount: Balance,
+        pub loan_tier3_max_amount: Balance,
+        pub loan_tier1_requirements: (u32, u32),
+        pub loan_tier2_requirements: (u32, u32),
+        pub loan_tier3_requirements: (u32, u32),
+        pub star_discount_percent_per_star: u64,
+        pub max_star_discount_percent: u64,
+        pub term_premium: (u64, Timestamp),
+        pub stress_requirements: (u64, u32),
+        pub inactivity_surcharge: (Timestamp, u32),
+        pub effective_reputation: (u32, bool),
+        pub interest_free_period: Timestamp,
+        pub loan_cooldown: Timestamp,
+        pub default_cooldown: Timestamp,
+        pub default_grace_period: Timestamp,
+        pub appeal_window: Timestamp,
+        pub term_penalty: (u32, Timestamp),
+        pub liquidation_bounty: Balance,
+        pub max_loan_extensions: u32,
+        pub extension_fee_percent: u64,
+        pub max_installments: u32,
+        pub max_vouchers_per_loan: u32,
+        pub min_vouch_age: Timestamp,
+        pub repay_reward_curve: (u32, Balance, Timestamp, u32),
     }
 
     /// All information that is needed to store in the contract
@@ -36,6 +97,16 @@ mod loan_manager {
         lending_pool_address: Address,
         loans: Mapping<u64, Loan>,
         next_loan_id: u64,
+        last_loan_time: Mapping<AccountId, Timestamp>, // borrower -> time of their last loan request
+        borrower_overrides: Mapping<AccountId, (u32, u32)>, // borrower -> (min_stars, min_vouches) replacing tier defaults
+        active_loan_count: u64, // Number of loans currently outstanding (Active or PendingDefault)
+        // Set of outstanding loan ids: dense index 0..active_loan_count -> loan id, and loan id -> index
+        active_loan_ids: Mapping<u64, u64>,
+        active_loan_positions: Mapping<u64, u64>,
+        pending_default_since: Mapping<u64, Timestamp>, // loan id -> time it was marked overdue
+        lifetime_stats: Mapping<AccountId, (Balance, Balance, u32)>, // borrower -> (borrowed, repaid, defaults)
+        last_default_time: Mapping<AccountId, Timestamp>, // borrower -> time of their most recent default
+        borrower_loans: Mapping<AccountId, Vec<u64>>, // borrower -> ids of every loan they requested
     }
 
     /// Enum for Loan Status
@@ -45,7 +116,15 @@ mod loan_manager {
         Pending,  // Waiting for vouches
         Active,   // Funded and active
         Repaid,
-        Defaulted
+        Defaulted,
+        PendingDefault, // Marked overdue, awaiting finalize_default after the appeal window
+    }
+
+    impl LoanStatus {
+        /// Whether the loan's funds are still out: Active, or PendingDefault during the appeal window
+        pub fn is_outstanding(&self) -> bool {
+            matches!(self, LoanStatus::Active | LoanStatus::PendingDefault)
+        }
     }
 
     /// Events for lending pool actions
@@ -62,6 +141,17 @@ mod loan_manager {
         id: u64,
         borrower: AccountId,
         amount: Balance,
+        principal_paid: Balance, // Part of amount that repaid principal (10 decimals)
+        interest_paid: Balance, // Part of amount that paid interest (10 decimals)
+    }
+
+    #[ink(event)]
+    pub struct InstallmentPaid {
+        id: u64,
+        borrower: AccountId,
+        amount: Balance,
+        installments_paid: u32,
+        remaining: Balance,
     }
 
     #[ink(event)]
@@ -71,6 +161,44 @@ mod loan_manager {
         amount: Balance,
     }
 
+    #[ink(event)]
+    pub struct LoanMarkedOverdue {
+        id: u64,
+        borrower: AccountId,
+        marked_at: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct DefaultCancelled {
+        id: u64,
+        borrower: AccountId,
+        admin: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct LoanExtended {
+        id: u64,
+        borrower: AccountId,
+        new_term: Timestamp,
+        fee: Balance,
+    }
+
+    #[ink(event)]
+    pub struct AdminResolvedLoan {
+        id: u64,
+        borrower: AccountId,
+        success: bool,
+        admin: AccountId,
+    }
+
+    /// Emitted on every loan status change, alongside the more specific events
+    #[ink(event)]
+    pub struct LoanStatusChanged {
+        id: u64,
+        old_status: LoanStatus,
+        new_status: LoanStatus,
+    }
+
     /// Error types for the contract
     #[derive(Debug, PartialEq, Eq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
@@ -90,6 +218,22 @@ mod loan_manager {
         InvalidRepaymentAmount,
         InsufficientAmount,
         Overflow,
+        ExceedsTierLimit,
+        ExtensionLimitReached,
+        InvalidExtensionFee,
+        FeeTransferFailed,
+        TooManyVouchers,
+        LoanCooldownActive,
+        DefaultCooldownActive,
+        LoanNotPendingDefault,
+        AppealWindowActive,
+        InvalidRecipient,
+        InsufficientLiquidity,
+        ZeroInstallments,
+        TooManyInstallments,
+        RateTooHigh,
+        BufferViolation,
+        GlobalCapExceeded,
     }
 
     pub type Result<T> = core::result::Result<T, Error>;
@@ -105,10 +249,6 @@ mod loan_manager {
                 .unwrap_or(0) as Balance
         }
 
-        // Token decimals constant - matches the scaling factor used throughout the contract (1e9)
-        // This should match the native currency's decimal places
-        const TOKEN_DECIMALS: Balance = 1_000_000_000; // 1e9
-
         #[ink(constructor)]
         pub fn new(config_address: Address, reputation_address: Address, lending_pool_address: Address, vouch_address: Address) -> Self {
             let config: ConfigRef =
@@ -127,41 +267,56 @@ mod loan_manager {
                 lending_pool_address,
                 loans: Mapping::default(),
                 next_loan_id: 1,
+                last_loan_time: Mapping::default(),
+                borrower_overrides: Mapping::default(),
+                active_loan_count: 0,
+                active_loan_ids: Mapping::default(),
+                active_loan_positions: Mapping::default(),
+                pending_default_since: Mapping::default(),
+                lifetime_stats: Mapping::default(),
+                last_default_time: Mapping::default(),
+                borrower_loans: Mapping::default(),
             }
         }
 
         /// Request a loan from the lending pool
         /// Creates a pending loan that requires vouches before disbursement
+        /// `num_installments` splits the repayment into equal payments spread over the term
+        /// (1 means a single payment at term; 0, counts above Config's max_installments and counts
+        /// above the amount, which would make installments worth zero, are rejected)
         #[ink(message)]
-        pub fn request_loan(&mut self, amount: Balance, loan_term: Timestamp, account_id: AccountId) -> Result<u64> {
-            if amount == 0 {
-                return Err(Error::ZeroAmount);
-            }
+        pub fn request_loan(&mut self, amount: Balance, loan_term: Timestamp, num_installments: u32, account_id: AccountId) -> Result<u64> {
             let caller: AccountId = account_id;
 
-            // Calculate tier-based requirements for this loan amount
-            let (min_stars, _min_vouches) = self.calculate_requirements(amount);
+            if num_installments == 0 {
+                return Err(Error::ZeroInstallments);
+            }
+            if num_installments > self.config.get_max_installments() {
+                return Err(Error::TooManyInstallments);
+            }
+
+            // Run every origination check; returns the borrower's own stars for rate-setting
+            let stars = self.check_origination(caller, amount)?;
 
-            // Verify stars via reputation contract (still required for loan request)
-            let stars = self.reputation.get_stars(caller);
-            if stars < min_stars {
-               return Err(Error::InsufficientReputation);
+            // Every installment must be worth at least one unit of principal, never zero
+            if num_installments as Balance > amount {
+                return Err(Error::TooManyInstallments);
             }
+            let now = self.env().block_timestamp();
 
-            // Fetch current rate from lending pool and adjust by stars
-            let base_rate = self.lending_pool.get_current_rate();
-            let adjusted_rate = self.adjust_rate_by_stars(base_rate, stars);
+            // Fetch the accrual rate (smoothed when enabled) from lending pool and adjust by stars and term
+            let base_rate = self.lending_pool.get_accrual_rate();
+            let adjusted_rate = self.origination_rate(base_rate, stars, loan_term);
 
-            // Calculate total repayment amount (principal + interest) at loan creation
-            // Repayment = amount * (1 + interest_rate_percentage)
-            // The interest_rate is stored as percentage * 1e9 (e.g., 10% = 10_000_000_000)
-            // To get the percentage: adjusted_rate / 100_000_000_000 (1e11)
-            // So: repayment = amount * (1 + adjusted_rate / 100_000_000_000)
-            // Example: 100 tokens at 10% = 100 * (1 + 10_000_000_000 / 100_000_000_000) = 100 * 1.10 = 110
-            let total_repayment = (amount as u128)
-                .checked_mul(100_000_000_000u128 + adjusted_rate as u128)
-                .and_then(|v| v.checked_div(100_000_000_000u128))
-                .unwrap_or(amount) as Balance;
+            // Refuse to lock the borrower into an extreme rate; they need more stars first
+            let loan_max_rate = self.config.get_loan_max_rate();
+            if loan_max_rate > 0 && adjusted_rate > loan_max_rate {
+                return Err(Error::RateTooHigh);
+            }
+
+            // Calculate total repayment amount (principal + interest) at loan creation,
+            // fixed from the rate snapshotted on the loan
+            let total_repayment = self.calculate_total_repayment(amount, adjusted_rate);
 
             // Create pending loan record (no vouches yet, no disbursement)
             let loan_id = self.next_loan_id;
@@ -174,11 +329,22 @@ mod loan_manager {
                 amount: amount,
                 borrower: caller,
                 total_repayment_amount: total_repayment,
+                extensions: 0,
+                num_installments,
+                installments_paid: 0,
+                repaid_amount: 0,
+                vouchers: Vec::new(),
             };
 
             // Store the loan
             self.loans.insert(loan_id, &loan);
             self.next_loan_id = loan_id + 1;
+            self.last_loan_time.insert(caller, &now);
+
+            // Index the loan under its borrower so per-borrower queries don't scan every loan
+            let mut loan_ids = self.borrower_loans.get(caller).unwrap_or_default();
+            loan_ids.push(loan_id);
+            self.borrower_loans.insert(caller, &loan_ids);
 
             // Emit LoanRequested event
             self.env().emit_event(LoanRequested {
@@ -202,13 +368,20 @@ mod loan_manager {
                 return Err(Error::LoanNotPending);
             }
 
+            // Bound the vouchers a single loan consumes so a default cannot cascade across all of them;
+            // every recorded voucher counts here, including ones no longer in good standing
+            if self.vouch.get_vouchers_for_loan(loan_id).len() as u32 >= self.config.get_max_vouchers_per_loan() {
+                return Err(Error::TooManyVouchers);
+            }
+
             // Create vouch via vouch contract
             self.vouch.vouch_for_loan(loan_id, loan.borrower, voucher_account_id, stars, capital_percent, loan_manager_address)
                 .map_err(|_| Error::ResolveFailed)?;
 
             // Check if we now have enough vouches to disburse
-            let (_min_stars, min_vouches) = self.calculate_requirements(loan.amount);
-            let current_vouches = self.vouch.get_vouches_for_loan(loan_id);
+            // Vouches younger than min_vouch_age don't count; such loans are disbursed later via try_disburse
+            let (_min_stars, min_vouches) = self.calculate_requirements(loan.borrower, loan.amount);
+            let current_vouches = self.count_eligible_vouches(loan_id, loan.borrower);
 
             if current_vouches >= min_vouches {
                 // Auto-disburse when threshold is met
@@ -218,6 +391,118 @@ mod loan_manager {
             Ok(())
         }
 
+        /// Dry-run the origination checks of `request_loan` for a borrower and amount
+        /// Returns Ok if a request would be accepted now, or the first failing check's error.
+        /// Then runs the pool's disbursement checks: InsufficientLiquidity, BufferViolation or
+        /// GlobalCapExceeded if the pool couldn't currently fund the amount.
+        #[ink(message)]
+        pub fn can_borrow(&self, borrower: AccountId, amount: Balance) -> Result<()> {
+            self.check_origination(borrower, amount)?;
+            self.lending_pool.can_disburse(amount).map_err(Self::disburse_refusal)
+        }
+
+        /// Internal: Loan manager error for a pool refusing to disburse
+        fn disburse_refusal(error: LendingPoolError) -> Error {
+            match error {
+                LendingPoolError::BufferViolation => Error::BufferViolation,
+                LendingPoolError::GlobalCapExceeded => Error::GlobalCapExceeded,
+                _ => Error::InsufficientLiquidity,
+            }
+        }
+
+        /// Get the (min_stars, min_vouches) a borrower currently needs to borrow `amount`
+        /// The borrower's override or the tier defaults, with the star minimum raised by the
+        /// inactivity surcharge and stress multiplier exactly as `request_loan` enforces it
+        #[ink(message)]
+        pub fn requirements_for(&self, borrower: AccountId, amount: Balance) -> (u32, u32) {
+            let (_min_stars, min_vouches) = self.calculate_requirements(borrower, amount);
+            (self.required_stars(borrower, amount), min_vouches)
+        }
+
+        /// Internal: Origination checks shared by `request_loan` and `can_borrow`
+        /// Amount, cooldowns, tier ceiling and star requirements. Returns the borrower's own stars.
+        fn check_origination(&self, borrower: AccountId, amount: Balance) -> Result<u32> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            // Enforce a minimum time between loan requests to discourage wash-borrowing
+            let now = self.env().block_timestamp();
+            if let Some(last_loan_time) = self.last_loan_time.get(borrower) {
+                if now.saturating_sub(last_loan_time) < self.config.get_loan_cooldown() {
+                    return Err(Error::LoanCooldownActive);
+                }
+            }
+
+            // A borrower who recently defaulted stays out of the pool for the default cooldown
+            if let Some(last_default_time) = self.last_default_time.get(borrower) {
+                if now.saturating_sub(last_default_time) < self.config.get_default_cooldown() {
+                    return Err(Error::DefaultCooldownActive);
+                }
+            }
+
+            // ...and so does one whose default is still in its appeal window
+            let pending_default = self.borrower_loans.get(borrower).unwrap_or_default().into_iter()
+                .any(|loan_id| self.loans.get(loan_id).is_some_and(|loan| loan.status == LoanStatus::PendingDefault));
+            if pending_default {
+                return Err(Error::DefaultCooldownActive);
+            }
+
+            // Reject amounts above the ceiling of the highest tier the borrower's stars qualify for
+            let stars = self.reputation.get_stars(borrower);
+            let tier_stars = self.tier_check_stars(borrower, stars);
+            if amount > self.borrower_tier_limit(borrower, tier_stars) {
+                return Err(Error::ExceedsTierLimit);
+            }
+
+            // Verify stars via reputation contract (still required for loan request)
+            if tier_stars < self.required_stars(borrower, amount) {
+               return Err(Error::InsufficientReputation);
+            }
+
+            Ok(stars)
+        }
+
+        /// How many more stars a borrower needs to request `amount` (0 if they already qualify)
+        /// Uses the same star requirement and star count as `request_loan`
+        #[ink(message)]
+        pub fn stars_shortfall(&self, borrower: AccountId, amount: Balance) -> u32 {
+            let stars = self.reputation.get_stars(borrower);
+            self.required_stars(borrower, amount)
+                .saturating_sub(self.tier_check_stars(borrower, stars))
+        }
+
+        /// How many more active vouches a borrower needs for `amount` (0 if satisfied)
+        /// Counts the borrower's Active vouches from vouchers in good standing; a pending loan's
+        /// disbursement additionally requires those vouches to be for that loan and old enough.
+        #[ink(message)]
+        pub fn vouch_shortfall(&self, borrower: AccountId, amount: Balance) -> u32 {
+            let (_min_stars, min_vouches) = self.calculate_requirements(borrower, amount);
+            min_vouches.saturating_sub(self.vouch.get_vouches_for(borrower))
+        }
+
+        /// Internal: Star requirement to originate `amount` for a borrower
+        /// The tier requirement (or override), raised if the borrower has been inactive for too
+        /// long or the pool is under stress
+        fn required_stars(&self, borrower: AccountId, amount: Balance) -> u32 {
+            let (min_stars, _min_vouches) = self.calculate_requirements(borrower, amount);
+            let min_stars = self.apply_inactivity_surcharge(borrower, min_stars);
+            self.apply_stress_multiplier(min_stars)
+        }
+
+        /// Disburse a pending loan once enough of its vouches have aged past min_vouch_age
+        /// Callable by anyone; all requirements are re-verified before disbursement
+        #[ink(message)]
+        pub fn try_disburse(&mut self, loan_id: u64) -> Result<()> {
+            self.disburse_loan(loan_id)
+        }
+
+        /// Internal: Count a loan's active vouches that are at least min_vouch_age old
+        fn count_eligible_vouches(&self, loan_id: u64, borrower: AccountId) -> u32 {
+            let min_vouch_age = self.config.get_min_vouch_age();
+            self.vouch.get_aged_vouches_for_loan(loan_id, borrower, min_vouch_age)
+        }
+
         // Internal function to disburse a loan that has enough vouches
         // Verifies all requirements: stars, vouches, and capital before disbursing
         fn disburse_loan(&mut self, loan_id: u64) -> Result<()> {
@@ -228,16 +513,16 @@ mod loan_manager {
             }
 
             // Re-verify all requirements before disbursement
-            let (min_stars, min_vouches) = self.calculate_requirements(loan.amount);
+            let (min_stars, min_vouches) = self.calculate_requirements(loan.borrower, loan.amount);
 
             // 1. Verify borrower still has enough stars (may have decreased since request)
             let borrower_stars = self.reputation.get_stars(loan.borrower);
-            if borrower_stars < min_stars {
+            if self.tier_check_stars(loan.borrower, borrower_stars) < min_stars {
                 return Err(Error::InsufficientReputation);
             }
 
-            // 2. Verify we have enough vouches
-            let current_vouches = self.vouch.get_vouches_for_loan(loan_id);
+            // 2. Verify we have enough vouches old enough to count
+            let current_vouches = self.count_eligible_vouches(loan_id, loan.borrower);
             if current_vouches < min_vouches {
                 return Err(Error::InsufficientVouches);
             }
@@ -254,9 +539,24 @@ mod loan_manager {
             }
 
             // All requirements met - update loan to Active status
-            loan.status = LoanStatus::Active;
+            // and record the vouchers backing it; only these are resolved when the loan concludes
+            self.transition(&mut loan, LoanStatus::Active);
             loan.start_time = self.env().block_timestamp();
+            loan.vouchers = self.vouch.get_vouchers_for_loan(loan_id);
             self.loans.insert(loan_id, &loan);
+            self.update_lifetime_stats(loan.borrower, loan.amount, 0, 0);
+
+            // Funds go to the borrower who requested this loan
+            self.pool_disburse(&loan, loan.borrower)
+        }
+
+        /// Internal: Ask the lending pool to send a loan's amount to `to`
+        /// Funds only ever go to the borrower who requested the loan: `to` is exactly the
+        /// address handed to the pool, and anything but `loan.borrower` is refused first
+        fn pool_disburse(&self, loan: &Loan, to: AccountId) -> Result<()> {
+            if to != loan.borrower {
+                return Err(Error::InvalidRecipient);
+            }
 
             // Disburse funds via lending pool using build_call for proper cross-contract execution
             use ink::env::call::{build_call, ExecutionInput, Selector};
@@ -267,7 +567,7 @@ mod loan_manager {
                 .exec_input(
                     ExecutionInput::new(Selector::new(ink::selector_bytes!("disburse")))
                         .push_arg(&loan.amount) // amount in 10 decimals (storage format)
-                        .push_arg(&loan.borrower) // borrower account
+                        .push_arg(&to) // borrower account
                 )
                 .returns::<Result<()>>()
                 .try_invoke();
@@ -280,107 +580,445 @@ mod loan_manager {
             Ok(())
         }
 
+        /// Deployment smoke-test: check that every dependency reads from the same Config
+        /// Returns true if Reputation, LendingPool and Vouch all point at this contract's Config
+        #[ink(message)]
+        pub fn verify_wiring(&self) -> bool {
+            let config_address = ink::ToAddr::to_addr(&self.config);
+            self.reputation.get_config_address() == config_address
+                && self.lending_pool.get_config_address() == config_address
+                && self.vouch.get_config_address() == config_address
+        }
+
+        /// Get every Config parameter this contract consumes, read through its ConfigRef
+        /// Lets integrators check the contract sees the expected values
+        #[ink(message)]
+        pub fn dependent_config(&self) -> DependentConfig {
+            DependentConfig {
+                admin: self.config.get_admin(),
+                rate_scale: self.config.get_rate_scale(),
+                max_rate: self.config.get_max_rate(),
+                loan_max_rate: self.config.get_loan_max_rate(),
+                amount_scale: self.config.get_amount_scale(),
+                loan_tier_scaling_factor: self.config.loan_tier_scaling_factor(),
+                loan_tier1_max_scaled_amount: self.config.loan_tier1_max_scaled_amount(),
+                loan_tier2_max_scaled_amount: self.config.loan_tier2_max_scaled_amount(),
+                loan_tier1_max_amount: self.config.loan_tier1_max_amount(),
+                loan_tier2_max_amount: self.config.loan_tier2_max_amount(),
+                loan_tier3_max_amount: self.config.loan_tier3_max_amount(),
+                loan_tier1_requirements: self.config.loan_tier1_requirements(),
+                loan_tier2_requirements: self.config.loan_tier2_requirements(),
+                loan_tier3_requirements: self.config.loan_tier3_requirements(),
+                star_discount_percent_per_star: self.config.get_star_discount_percent_per_star(),
+                max_star_discount_percent: self.config.get_max_star_discount_percent(),
+                term_premium: self.config.get_term_premium(),
+                stress_requirements: self.config.get_stress_requirements(),
+                inactivity_surcharge: self.config.get_inactivity_surcharge(),
+                effective_reputation: self.config.get_effective_reputation(),
+                interest_free_period: self.config.get_interest_free_period(),
+                loan_cooldown: self.config.get_loan_cooldown(),
+                default_cooldown: self.config.get_default_cooldown(),
+                default_grace_period: self.config.get_default_grace_period(),
+                appeal_window: self.config.get_appeal_window(),
+                term_penalty: self.config.get_term_penalty(),
+                liquidation_bounty: self.config.get_liquidation_bounty(),
+                max_loan_extensions: self.config.get_max_loan_extensions(),
+                extension_fee_percent: self.config.get_extension_fee_percent(),
+                max_installments: self.config.get_max_installments(),
+                max_vouchers_per_loan: self.config.get_max_vouchers_per_loan(),
+                min_vouch_age: self.config.get_min_vouch_age(),
+                repay_reward_curve: self.config.get_repay_reward_curve(),
+            }
+        }
+
         // Get loan information (for external queries)
         #[ink(message)]
         pub fn get_loan(&self, loan_id: u64) -> Option<Loan> {
             self.loans.get(loan_id)
         }
 
+        /// Get an aggregated underwriting profile for a borrower
+        /// Combines stars (Reputation), active vouch stats (Vouch) and active loans (LoanManager)
+        #[ink(message)]
+        pub fn get_borrower_profile(&self, borrower: AccountId) -> BorrowerProfile {
+            let stars = self.reputation.get_stars(borrower);
+            let (active_vouches, vouched_capital, _staked_stars) = self.vouch.get_borrower_vouch_summary(borrower);
+            let (active_loans, outstanding_principal) = self.outstanding_loans(borrower);
+
+            BorrowerProfile {
+                stars,
+                active_vouches,
+                vouched_capital,
+                active_loans,
+                outstanding_principal,
+            }
+        }
+
+        /// Internal: Count a borrower's outstanding loans and the principal still owed on them
+        fn outstanding_loans(&self, borrower: AccountId) -> (u32, Balance) {
+            let mut active_loans: u32 = 0;
+            let mut outstanding_principal: Balance = 0;
+            for loan_id in self.borrower_loans.get(borrower).unwrap_or_default() {
+                if let Some(loan) = self.loans.get(loan_id) {
+                    if loan.status.is_outstanding() {
+                        active_loans += 1;
+                        outstanding_principal = outstanding_principal
+                            .saturating_add(self.calculate_outstanding_principal(&loan));
+                    }
+                }
+            }
+            (active_loans, outstanding_principal)
+        }
+
+        /// Get a borrower's lifetime credit summary
+        /// Returns (total disbursed principal, total repaid incl. interest, defaults), amounts in 10 decimals
+        #[ink(message)]
+        pub fn get_lifetime_stats(&self, borrower: AccountId) -> (Balance, Balance, u32) {
+            self.lifetime_stats.get(borrower).unwrap_or((0, 0, 0))
+        }
+
+        /// Internal: Add to a borrower's lifetime borrowed, repaid and default counters
+        fn update_lifetime_stats(&mut self, borrower: AccountId, borrowed: Balance, repaid: Balance, defaults: u32) {
+            let (total_borrowed, total_repaid, total_defaults) = self.get_lifetime_stats(borrower);
+            self.lifetime_stats.insert(borrower, &(
+                total_borrowed.saturating_add(borrowed),
+                total_repaid.saturating_add(repaid),
+                total_defaults.saturating_add(defaults),
+            ));
+        }
+
+        /// Get the ids of every loan a borrower has requested, oldest first
+        #[ink(message)]
+        pub fn get_borrower_loans(&self, borrower: AccountId) -> Vec<u64> {
+            self.borrower_loans.get(borrower).unwrap_or_default()
+        }
+
+        /// Get the borrower's largest outstanding (Active or PendingDefault) loan as (loan_id, amount in 10 decimals)
+        /// Returns None if the borrower has no outstanding loan; ties go to the oldest loan
+        #[ink(message)]
+        pub fn largest_active_loan(&self, borrower: AccountId) -> Option<(u64, Balance)> {
+            let mut largest: Option<(u64, Balance)> = None;
+            for loan_id in self.borrower_loans.get(borrower).unwrap_or_default() {
+                if let Some(loan) = self.loans.get(loan_id) {
+                    if !loan.status.is_outstanding() {
+                        continue;
+                    }
+                    if largest.map_or(true, |(_, amount)| loan.amount > amount) {
+                        largest = Some((loan_id, loan.amount));
+                    }
+                }
+            }
+            largest
+        }
+
+        /// Get a borrower's effective reputation: own stars plus `vouch_star_weight_percent`
+        /// of the stars staked on them by active vouchers
+        #[ink(message)]
+        pub fn effective_reputation(&self, borrower: AccountId) -> u32 {
+            let stars = self.reputation.get_stars(borrower);
+            self.blend_vouch_stars(borrower, stars)
+        }
+
+        /// Internal: Add the weighted stars of a borrower's active vouchers to `stars`
+        fn blend_vouch_stars(&self, borrower: AccountId, stars: u32) -> u32 {
+            let (weight_percent, _enabled) = self.config.get_effective_reputation();
+            let (_count, _capital, staked_stars) = self.vouch.get_borrower_vouch_summary(borrower);
+            let vouch_stars = (staked_stars as u64).saturating_mul(weight_percent as u64) / 100;
+            (stars as u64).saturating_add(vouch_stars).min(u32::MAX as u64) as u32
+        }
+
+        /// Internal: Stars compared against tier minimums
+        /// The borrower's own stars, or their effective reputation when Config enables it
+        fn tier_check_stars(&self, borrower: AccountId, stars: u32) -> u32 {
+            let (_weight_percent, enabled) = self.config.get_effective_reputation();
+            if enabled {
+                self.blend_vouch_stars(borrower, stars)
+            } else {
+                stars
+            }
+        }
+
+        /// Get the vouchers recorded on a loan, i.e. those exposed to its default risk
+        /// Returns an empty list for unknown loans and for loans that are not yet disbursed
+        #[ink(message)]
+        pub fn get_loan_vouchers(&self, loan_id: u64) -> Vec<AccountId> {
+            self.loans.get(loan_id)
+                .map(|loan| loan.vouchers)
+                .unwrap_or_default()
+        }
+
         // Repay a loan
-        // Calculates the repayment amount (principal + interest) and processes the repayment
+        // Calculates the outstanding repayment amount (principal + interest) and processes the repayment
         // Marks the loan as repaid and resolves vouches as successful
+        // A PendingDefault loan can still be repaid during its appeal window, which cures the default
         #[ink(message, payable)]
         pub fn repay_loan(&mut self, loan_id: u64, borrower_account_id: AccountId, loan_manager_address: Address) -> Result<()> {
             let mut loan = self.loans.get(loan_id).ok_or(Error::LoanNotFound)?;
+            Self::ensure_repayable(&loan)?;
 
-            // Only active loans can be repaid
-            if loan.status != LoanStatus::Active {
-                return Err(Error::LoanNotActive);
+            // Verify caller is the borrower
+            if borrower_account_id != loan.borrower {
+                return Err(Error::Unauthorized);
             }
 
+            // Calculate the outstanding amount owed (principal + interest accrued so far, or
+            // principal only inside the interest-free window), split as owed_breakdown splits it
+            let elapsed = self.env().block_timestamp().saturating_sub(loan.start_time);
+            let (principal_paid, interest_due) =
+                self.breakdown_at(&loan, elapsed, self.config.get_interest_free_period());
+            let repayment_amount = principal_paid.saturating_add(interest_due);
+
+            // Verify the transferred value and forward it to the lending pool
+            self.forward_repayment(repayment_amount)?;
+            self.cure_pending_default(&mut loan);
+
+            loan.repaid_amount = loan.repaid_amount.saturating_add(repayment_amount);
+            self.update_lifetime_stats(loan.borrower, 0, repayment_amount, 0);
+            loan.installments_paid = loan.num_installments;
+            self.settle_repaid_loan(loan, repayment_amount, principal_paid, loan_manager_address)
+        }
+
+        /// Repay the next installment of a loan
+        /// Each installment is an equal share of the total repayment (principal plus interest);
+        /// the final installment also covers any rounding remainder.
+        /// The loan is only marked as repaid once the final installment is paid.
+        /// Paying an installment on a PendingDefault loan returns it to Active.
+        #[ink(message, payable)]
+        pub fn repay_installment(&mut self, loan_id: u64, borrower_account_id: AccountId, loan_manager_address: Address) -> Result<()> {
+            let mut loan = self.loans.get(loan_id).ok_or(Error::LoanNotFound)?;
+            Self::ensure_repayable(&loan)?;
+
             // Verify caller is the borrower
             if borrower_account_id != loan.borrower {
                 return Err(Error::Unauthorized);
             }
 
-            // Calculate repayment amount (principal + interest)
-            let repayment_amount = self.calculate_repayment_amount(&loan);
+            let installment = self.calculate_installment_amount(&loan);
+
+            // Verify the transferred value and forward it to the lending pool
+            self.forward_repayment(installment)?;
+            self.cure_pending_default(&mut loan);
+
+            loan.repaid_amount = loan.repaid_amount.saturating_add(installment);
+            self.update_lifetime_stats(loan.borrower, 0, installment, 0);
+            loan.installments_paid = loan.installments_paid.saturating_add(1);
+
+            if loan.installments_paid >= loan.num_installments {
+                // The event covers every installment, so the whole principal was repaid
+                let principal_paid = loan.amount.min(loan.repaid_amount);
+                return self.settle_repaid_loan(loan, loan.repaid_amount, principal_paid, loan_manager_address);
+            }
+
+            self.loans.insert(loan_id, &loan);
+
+            self.env().emit_event(InstallmentPaid {
+                id: loan_id,
+                borrower: loan.borrower,
+                amount: installment,
+                installments_paid: loan.installments_paid,
+                remaining: self.calculate_repayment_amount(&loan),
+            });
+
+            Ok(())
+        }
+
+        /// Internal: Only outstanding loans can be repaid: Active ones, and PendingDefault ones
+        /// during their appeal window, where a repayment cures the default
+        fn ensure_repayable(loan: &Loan) -> Result<()> {
+            if !loan.status.is_outstanding() {
+                return Err(Error::LoanNotActive);
+            }
+            Ok(())
+        }
+
+        /// Internal: Return a PendingDefault loan to Active once the borrower has paid
+        /// A full repayment then settles it as usual; after an installment `mark_overdue` can mark
+        /// it again if the next installment is already overdue. The caller persists the loan.
+        fn cure_pending_default(&mut self, loan: &mut Loan) {
+            if loan.status == LoanStatus::PendingDefault {
+                self.transition(loan, LoanStatus::Active);
+                self.pending_default_since.remove(loan.loan_id);
+            }
+        }
 
+        /// Internal: Verify the transferred value matches `amount` (10 decimals) and
+        /// forward it to the lending pool's receive_repayment
+        fn forward_repayment(&self, amount: Balance) -> Result<()> {
             let repaid_u256 = self.env().transferred_value();
             if repaid_u256 > U256::from(u128::MAX) {
                 return Err(Error::Overflow);
             }
             let repaid: Balance = repaid_u256.as_u128();
 
-            // Convert repayment_amount from 10 decimals to 18 decimals for comparison
+            // Convert the amount from 10 decimals to 18 decimals for comparison
             // The chain uses 18 decimals, but our contract uses 10 decimals
-            const DECIMAL_CONVERSION: u128 = 100_000_000; // 10^8 (to convert from 10 to 18 decimals)
-            let repayment_amount_18_decimals = (repayment_amount as u128)
-                .checked_mul(DECIMAL_CONVERSION)
-                .unwrap_or(0) as Balance;
+            let amount_18_decimals = self.convert_10_to_18_decimals(amount);
 
             // Verify the repaid amount matches the required repayment (both in 18 decimals)
-            if repaid != repayment_amount_18_decimals {
+            if repaid != amount_18_decimals {
                 return Err(Error::InvalidRepaymentAmount);
             }
 
             // Forward payment to lending pool's receive_repayment
-            // Use build_call to forward the payment with the repayment amount
-            // Note: repaid is in 18 decimals, but we need to pass the amount parameter in the same decimals
             // The lending pool expects the amount parameter to match the transferred value (18 decimals)
             use ink::env::call::{build_call, ExecutionInput, Selector};
             use ink::env::DefaultEnvironment;
-            
-            // Use the repaid amount (18 decimals) for forwarding, and pass it as the amount parameter
-            let repayment_u256 = U256::from(repaid);
-            
+
             let result = build_call::<DefaultEnvironment>()
                 .call(self.lending_pool_address)
-                .transferred_value(repayment_u256)
+                .transferred_value(U256::from(repaid))
                 .exec_input(
                     ExecutionInput::new(Selector::new(ink::selector_bytes!("receive_repayment")))
                         .push_arg(&repaid) // Pass repaid (18 decimals) to match transferred value
                 )
                 .returns::<Result<()>>()
                 .try_invoke();
-            
+
             match result {
-                Ok(Ok(_)) => {},
-                _ => return Err(Error::RepaymentFailed),
+                Ok(Ok(_)) => Ok(()),
+                _ => Err(Error::RepaymentFailed),
             }
+        }
 
+        /// Internal: Mark a fully repaid loan as repaid, record it and resolve its vouches
+        fn settle_repaid_loan(&mut self, mut loan: Loan, amount: Balance, principal_paid: Balance, loan_manager_address: Address) -> Result<()> {
             // Mark loan as repaid
-            loan.status = LoanStatus::Repaid;
-            self.loans.insert(loan_id, &loan);
+            self.transition(&mut loan, LoanStatus::Repaid);
+            self.loans.insert(loan.loan_id, &loan);
+
+            // Record the successful loan in the borrower's reputation history
+            let _ = self.reputation.record_loan(loan.borrower, loan.amount, true);
+
+            // Reward the borrower with stars scaled by loan size and term
+            let reward = self.calculate_repay_reward(&loan);
+            if reward > 0 {
+                let _ = self.reputation.add_stars(loan.borrower, reward);
+            }
 
-            // Resolve all vouch relationships for this loan as successful
+            // Resolve the vouch relationships recorded on this loan as successful
             // Pass 0 for loan_amount since it's not used when success=true
-            self.vouch.resolve_loan(loan_id, loan.borrower, true, 0, loan_manager_address)
+            self.vouch.resolve_loan(loan.loan_id, loan.borrower, loan.vouchers.clone(), true, 0, loan_manager_address)
                 .map_err(|_| Error::ResolveFailed)?;
 
             // Emit LoanRepaid event
-            self.env().emit_event(LoanRepaid {
+            self.env().emit_event(Self::loan_repaid_event(&loan, amount, principal_paid));
+
+            Ok(())
+        }
+
+        /// Internal: LoanRepaid for `amount` paid on a loan, `principal_paid` of it repaying
+        /// principal and the rest interest
+        fn loan_repaid_event(loan: &Loan, amount: Balance, principal_paid: Balance) -> LoanRepaid {
+            LoanRepaid {
+                id: loan.loan_id,
+                borrower: loan.borrower,
+                amount,
+                principal_paid,
+                interest_paid: amount.saturating_sub(principal_paid),
+            }
+        }
+
+        /// Extend the term of an active loan
+        /// Callable by the borrower before the loan is defaulted. The extension fee
+        /// (a percentage of principal) must be transferred with the call and goes to the pool reserves.
+        /// The borrower must still meet the star requirement of the loan's tier.
+        #[ink(message, payable)]
+        pub fn extend_loan(&mut self, loan_id: u64, extra_term: Timestamp, borrower_account_id: AccountId) -> Result<()> {
+            let mut loan = self.loans.get(loan_id).ok_or(Error::LoanNotFound)?;
+
+            // Only active loans can be extended (defaulted or repaid loans are final)
+            if loan.status != LoanStatus::Active {
+                return Err(Error::LoanNotActive);
+            }
+
+            // Verify caller is the borrower
+            if borrower_account_id != loan.borrower {
+                return Err(Error::Unauthorized);
+            }
+
+            if extra_term == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            if loan.extensions >= self.config.get_max_loan_extensions() {
+                return Err(Error::ExtensionLimitReached);
+            }
+
+            // Re-check the borrower is still in good standing for this loan's tier
+            let (min_stars, _min_vouches) = self.calculate_requirements(loan.borrower, loan.amount);
+            let borrower_stars = self.reputation.get_stars(loan.borrower);
+            if self.tier_check_stars(loan.borrower, borrower_stars) < min_stars {
+                return Err(Error::InsufficientReputation);
+            }
+
+            // Verify the transferred value matches the extension fee (both in 18 decimals)
+            let fee = self.calculate_extension_fee(&loan);
+            let fee_18_decimals = self.convert_10_to_18_decimals(fee);
+            let paid_u256 = self.env().transferred_value();
+            if paid_u256 > U256::from(u128::MAX) {
+                return Err(Error::Overflow);
+            }
+            if paid_u256.as_u128() != fee_18_decimals {
+                return Err(Error::InvalidExtensionFee);
+            }
+
+            // Forward the fee to the lending pool reserves
+            if fee_18_decimals > 0 {
+                use ink::env::call::{build_call, ExecutionInput, Selector};
+                use ink::env::DefaultEnvironment;
+
+                let result = build_call::<DefaultEnvironment>()
+                    .call(self.lending_pool_address)
+                    .transferred_value(U256::from(fee_18_decimals))
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(ink::selector_bytes!("receive_extension_fee")))
+                            .push_arg(&fee_18_decimals)
+                    )
+                    .returns::<Result<()>>()
+                    .try_invoke();
+
+                match result {
+                    Ok(Ok(_)) => {},
+                    _ => return Err(Error::FeeTransferFailed),
+                }
+            }
+
+            loan.term = loan.term.saturating_add(extra_term);
+            loan.extensions = loan.extensions.saturating_add(1);
+            self.loans.insert(loan_id, &loan);
+
+            self.env().emit_event(LoanExtended {
                 id: loan_id,
                 borrower: loan.borrower,
-                amount: repayment_amount,
+                new_term: loan.term,
+                fee,
             });
 
             Ok(())
         }
 
-        /// Check if a loan is overdue and handle defaulting
+        /// Get the fee required to extend a loan
+        /// Returns value in 18 decimals (chain format), the exact value to send with `extend_loan`
+        #[ink(message)]
+        pub fn get_extension_fee(&self, loan_id: u64) -> Result<Balance> {
+            let loan = self.loans.get(loan_id).ok_or(Error::LoanNotFound)?;
+            Ok(self.convert_10_to_18_decimals(self.calculate_extension_fee(&loan)))
+        }
+
+        /// Mark an overdue loan as PendingDefault, opening the appeal window
         /// 
-        /// This function can be called by anyone to trigger default processing for overdue loans.
+        /// This function can be called by anyone to start default processing for overdue loans.
         /// It includes safeguards to prevent premature defaults:
-        /// - Only active loans can be defaulted (prevents double-processing)
+        /// - Only active loans can be marked (prevents double-processing)
         /// - Loan must be past due date + grace period (configurable buffer)
         /// 
         /// The grace period provides a buffer after the due date, allowing borrowers time to
         /// repay and preventing race conditions with repayment transactions.
         /// 
-        /// Slashes borrower's stars and resolves vouches as failed
+        /// Nothing is slashed yet: the borrower can contest during the appeal window and the
+        /// admin can reverse it with `cancel_default`; `finalize_default` completes the default.
         #[ink(message)]
-        pub fn check_default(&mut self, loan_id: u64, loan_manager_address: Address, vouch_contract_address: Address) -> Result<()> {
+        pub fn mark_overdue(&mut self, loan_id: u64) -> Result<()> {
             let mut loan = self.loans.get(loan_id).ok_or(Error::LoanNotFound)?;
 
             // Only active loans can be defaulted (prevents double-processing)
@@ -389,8 +1027,9 @@ mod loan_manager {
             }
 
             // Check if loan is overdue and past grace period
+            // For installment loans the due time is that of the next unpaid installment
             let current_time = self.env().block_timestamp();
-            let due_time = loan.start_time.saturating_add(loan.term);
+            let due_time = self.next_due_time(&loan);
             let grace_period = self.config.get_default_grace_period();
             let defaultable_time = due_time.saturating_add(grace_period);
             
@@ -400,18 +1039,45 @@ mod loan_manager {
                 return Err(Error::LoanNotOverdue);
             }
 
+            self.transition(&mut loan, LoanStatus::PendingDefault);
+            self.loans.insert(loan_id, &loan);
+            self.pending_default_since.insert(loan_id, &current_time);
+
+            self.env().emit_event(LoanMarkedOverdue {
+                id: loan_id,
+                borrower: loan.borrower,
+                marked_at: current_time,
+            });
+
+            Ok(())
+        }
+
+        /// Finalize the default of a loan once its appeal window has elapsed
+        /// Callable by anyone. Slashes borrower's stars and resolves vouches as failed.
+        /// The caller (keeper) is paid the configured liquidation bounty from the pool's keeper pool
+        /// when it can cover the bounty.
+        #[ink(message)]
+        pub fn finalize_default(&mut self, loan_id: u64, loan_manager_address: Address, vouch_contract_address: Address) -> Result<()> {
+            let mut loan = self.loans.get(loan_id).ok_or(Error::LoanNotFound)?;
+            let current_time = self.env().block_timestamp();
+            self.check_finalizable(&loan, current_time, self.config.get_appeal_window())?;
+
             // Mark loan as defaulted
-            loan.status = LoanStatus::Defaulted;
+            self.transition(&mut loan, LoanStatus::Defaulted);
             self.loans.insert(loan_id, &loan);
+            self.pending_default_since.remove(loan_id);
+            self.last_default_time.insert(loan.borrower, &current_time);
+            self.update_lifetime_stats(loan.borrower, 0, 0, 1);
 
             // Slash borrower's stars via reputation contract
-            // Slash amount proportional to loan amount, using consistent token decimals
-            let stars_to_slash = (loan.amount / Self::TOKEN_DECIMALS).max(1) as u32;
+            let stars_to_slash = self.default_star_slash(&loan, current_time);
             let _ = self.reputation.slash_stars(loan.borrower, stars_to_slash);
+            let _ = self.reputation.record_loan(loan.borrower, loan.amount, false);
 
-            // Resolve all vouch relationships for this loan as failed
+            // Resolve the vouch relationships recorded on this loan as failed; the borrower's
+            // vouchers for other loans are left untouched
             // Pass loan.amount to compare with slashed capital for recovery calculation
-            self.vouch.resolve_loan(loan_id, loan.borrower, false, loan.amount, loan_manager_address)
+            self.vouch.resolve_loan(loan_id, loan.borrower, loan.vouchers.clone(), false, loan.amount, loan_manager_address)
                 .map_err(|_| Error::ResolveFailed)?;
 
             // Emit LoanDefaulted event
@@ -421,38 +1087,381 @@ mod loan_manager {
                 amount: loan.amount,
             });
 
+            // Reward the keeper; skipped when the bounty is unset or the keeper pool is insufficient
+            let bounty = self.config.get_liquidation_bounty();
+            if bounty > 0 {
+                let _ = self.lending_pool.pay_keeper_bounty(self.env().caller(), bounty);
+            }
+
             Ok(())
         }
 
-        /// Internal: Calculate tier-based requirements for a loan amount
-        /// Returns (min_stars_required, min_vouches_required)
-        /// Tier thresholds and requirements are configurable via the Config contract.
-        /// This avoids hardcoded magic numbers and allows protocol upgrades without
-        /// redeploying the LoanManager.
-        fn calculate_requirements(&self, amount: Balance) -> (u32, u32) {
-            // Scaling factor used to normalize the loan amount before tier comparison
-            let scaling_factor = self.config.loan_tier_scaling_factor();
-            let scaled_amount = if scaling_factor > 0 {
-                amount / scaling_factor
-            } else {
-                // Fallback to no scaling if misconfigured; preserves previous behavior shape
+        /// Internal: A loan can be finalized as defaulted once it is PendingDefault and its
+        /// appeal window has elapsed at `now`
+        fn check_finalizable(&self, loan: &Loan, now: Timestamp, appeal_window: Timestamp) -> Result<()> {
+            if loan.status != LoanStatus::PendingDefault {
+                return Err(Error::LoanNotPendingDefault);
+            }
+            let marked_at = self.pending_default_since.get(loan.loan_id).unwrap_or(0);
+            if now.saturating_sub(marked_at) < appeal_window {
+                return Err(Error::AppealWindowActive);
+            }
+            Ok(())
+        }
+
+        /// Reverse a PendingDefault (e.g., a wrongful default upheld on appeal)
+        /// Only callable by the Config admin; the loan returns to Active unchanged
+        #[ink(message)]
+        pub fn cancel_default(&mut self, loan_id: u64) -> Result<()> {
+            let admin = self.ensure_admin()?;
+            self.apply_cancel_default(loan_id, admin)
+        }
+
+        /// Internal: Body of `cancel_default` past the admin check
+        fn apply_cancel_default(&mut self, loan_id: u64, admin: AccountId) -> Result<()> {
+            let mut loan = self.loans.get(loan_id).ok_or(Error::LoanNotFound)?;
+            if loan.status != LoanStatus::PendingDefault {
+                return Err(Error::LoanNotPendingDefault);
+            }
+
+            self.transition(&mut loan, LoanStatus::Active);
+            self.loans.insert(loan_id, &loan);
+            self.pending_default_since.remove(loan_id);
+
+            self.env().emit_event(DefaultCancelled {
+                id: loan_id,
+                borrower: loan.borrower,
+                admin,
+            });
+
+            Ok(())
+        }
+
+        /// Milliseconds until an Active loan's next payment is due, negative once overdue
+        /// For installment loans this is the next unpaid installment. None if the loan isn't Active.
+        #[ink(message)]
+        pub fn time_to_due(&self, loan_id: u64) -> Option<i64> {
+            let loan = self.loans.get(loan_id)?;
+            if loan.status != LoanStatus::Active {
+                return None;
+            }
+            let due_time = self.next_due_time(&loan) as i128;
+            let now = self.env().block_timestamp() as i128;
+            Some((due_time - now).clamp(i64::MIN as i128, i64::MAX as i128) as i64)
+        }
+
+        /// Get the time a loan was marked overdue, if it is awaiting finalize_default
+        #[ink(message)]
+        pub fn get_pending_default_since(&self, loan_id: u64) -> Option<Timestamp> {
+            self.pending_default_since.get(loan_id)
+        }
+
+        /// Force-resolve a stuck loan without payment (migrations, dispute resolution)
+        /// Only callable by the Config admin. Pending or active loans are marked Repaid (success)
+        /// or Defaulted (failure) and their vouchers are settled accordingly. For active loans
+        /// the unpaid principal is written off the pool's borrowed total.
+        /// The borrower's own reputation is left unchanged.
+        #[ink(message)]
+        pub fn admin_resolve_loan(&mut self, loan_id: u64, success: bool, loan_manager_address: Address) -> Result<()> {
+            let admin = self.ensure_admin()?;
+
+            let mut loan = self.loans.get(loan_id).ok_or(Error::LoanNotFound)?;
+            let was_active = match loan.status {
+                LoanStatus::Active | LoanStatus::PendingDefault => true,
+                LoanStatus::Pending => false,
+                _ => return Err(Error::LoanNotActive),
+            };
+            self.pending_default_since.remove(loan_id);
+
+            let new_status = if success { LoanStatus::Repaid } else { LoanStatus::Defaulted };
+            self.transition(&mut loan, new_status);
+            self.loans.insert(loan_id, &loan);
+            if !success {
+                self.last_default_time.insert(loan.borrower, &self.env().block_timestamp());
+                self.update_lifetime_stats(loan.borrower, 0, 0, 1);
+            }
+
+            // No repayment will arrive for an active loan, so clear its unpaid principal from the pool
+            if was_active {
+                let unpaid_principal = self.calculate_outstanding_principal(&loan);
+                if unpaid_principal > 0 {
+                    self.lending_pool.write_off_borrowed(unpaid_principal)
+                        .map_err(|_| Error::ResolveFailed)?;
+                }
+            }
+
+            // Settle the vouchers of this loan; on failure their stake covers the loan amount.
+            // A pending loan has no recorded vouchers yet, so release whoever vouched for it so far
+            let vouchers = if was_active { loan.vouchers.clone() } else { self.vouch.get_vouchers_for_loan(loan_id) };
+            self.vouch.resolve_loan(loan_id, loan.borrower, vouchers, success, loan.amount, loan_manager_address)
+                .map_err(|_| Error::ResolveFailed)?;
+
+            self.env().emit_event(AdminResolvedLoan {
+                id: loan_id,
+                borrower: loan.borrower,
+                success,
+                admin,
+            });
+
+            Ok(())
+        }
+
+        /// Override the tier requirements for a specific borrower (e.g., trusted institutions)
+        /// Only callable by the Config admin
+        #[ink(message)]
+        pub fn set_borrower_override(&mut self, borrower: AccountId, min_stars: u32, min_vouches: u32) -> Result<()> {
+            self.ensure_admin()?;
+            self.borrower_overrides.insert(borrower, &(min_stars, min_vouches));
+            Ok(())
+        }
+
+        /// Remove a borrower's requirement override, restoring the tier defaults
+        /// Only callable by the Config admin
+        #[ink(message)]
+        pub fn clear_borrower_override(&mut self, borrower: AccountId) -> Result<()> {
+            self.ensure_admin()?;
+            self.borrower_overrides.remove(borrower);
+            Ok(())
+        }
+
+        /// Get a borrower's requirement override as (min_stars, min_vouches), if any
+        #[ink(message)]
+        pub fn get_borrower_override(&self, borrower: AccountId) -> Option<(u32, u32)> {
+            self.borrower_overrides.get(borrower)
+        }
+
+        /// Internal: Verify the transaction caller is the Config admin, returning the admin account
+        fn ensure_admin(&self) -> Result<AccountId> {
+            let admin = self.config.get_admin();
+            if self.env().caller() != AccountIdMapper::to_address(admin.as_ref()) {
+                return Err(Error::Unauthorized);
+            }
+            Ok(admin)
+        }
+
+        /// Internal: Change the status of a loan and emit LoanStatusChanged
+        /// The caller is responsible for persisting the loan afterwards
+        fn transition(&mut self, loan: &mut Loan, new_status: LoanStatus) {
+            let old_status = loan.status;
+            loan.status = new_status;
+
+            // Keep the active loan set and count in step with every status change;
+            // a loan in its appeal window is still outstanding and stays in the set
+            if !old_status.is_outstanding() && new_status.is_outstanding() {
+                self.add_active_loan(loan.loan_id);
+            } else if old_status.is_outstanding() && !new_status.is_outstanding() {
+                self.remove_active_loan(loan.loan_id);
+            }
+
+            self.env().emit_event(LoanStatusChanged {
+                id: loan.loan_id,
+                old_status,
+                new_status,
+            });
+        }
+
+        /// Internal: Append a loan to the active set
+        fn add_active_loan(&mut self, loan_id: u64) {
+            let position = self.active_loan_count;
+            self.active_loan_ids.insert(position, &loan_id);
+            self.active_loan_positions.insert(loan_id, &position);
+            self.active_loan_count = position.saturating_add(1);
+        }
+
+        /// Internal: Remove a loan from the active set by moving the last entry into its slot
+        fn remove_active_loan(&mut self, loan_id: u64) {
+            let Some(position) = self.active_loan_positions.get(loan_id) else {
+                return;
+            };
+            let last = self.active_loan_count.saturating_sub(1);
+            if position != last {
+                if let Some(last_id) = self.active_loan_ids.get(last) {
+                    self.active_loan_ids.insert(position, &last_id);
+                    self.active_loan_positions.insert(last_id, &position);
+                }
+            }
+            self.active_loan_ids.remove(last);
+            self.active_loan_positions.remove(loan_id);
+            self.active_loan_count = last;
+        }
+
+        /// Internal: Determine which loan tier (1, 2 or 3) an amount falls into
+        /// Tier thresholds are configurable via the Config contract.
+        fn loan_tier(&self, amount: Balance) -> u8 {
+            Self::tier_for(
+                amount,
+                self.config.loan_tier_scaling_factor(),
+                self.config.loan_tier1_max_scaled_amount(),
+                self.config.loan_tier2_max_scaled_amount(),
+            )
+        }
+
+        /// Internal: Tier of an amount given the scaling factor and the scaled tier thresholds
+        fn tier_for(amount: Balance, scaling_factor: Balance, tier1_max: Balance, tier2_max: Balance) -> u8 {
+            // Scaling factor used to normalize the loan amount before tier comparison
+            let scaled_amount = if scaling_factor > 0 {
+                amount / scaling_factor
+            } else {
+                // Fallback to no scaling if misconfigured; preserves previous behavior shape
                 amount
             };
-            // Configurable tier thresholds expressed in the same scaled units
-            let tier1_max = self.config.loan_tier1_max_scaled_amount();
-            let tier2_max = self.config.loan_tier2_max_scaled_amount();
             if scaled_amount < tier1_max {
-                // Tier 1 requirements
-                self.config.loan_tier1_requirements()
+                1
             } else if scaled_amount < tier2_max {
-                // Tier 2 requirements
-                self.config.loan_tier2_requirements()
+                2
             } else {
-                // Tier 3 requirements
-                self.config.loan_tier3_requirements()
+                3
             }
         }
 
+        /// Internal: Calculate tier-based requirements for a loan amount
+        /// Returns (min_stars_required, min_vouches_required)
+        /// Tier thresholds and requirements are configurable via the Config contract.
+        /// This avoids hardcoded magic numbers and allows protocol upgrades without
+        /// redeploying the LoanManager.
+        /// A per-borrower override set by the admin takes precedence over the tier defaults.
+        fn calculate_requirements(&self, borrower: AccountId, amount: Balance) -> (u32, u32) {
+            if let Some(requirements) = self.borrower_overrides.get(borrower) {
+                return requirements;
+            }
+            self.tier_requirements(amount)
+        }
+
+        /// Internal: Tier default (min_stars, min_vouches) for a loan amount, before any override
+        fn tier_requirements(&self, amount: Balance) -> (u32, u32) {
+            match self.loan_tier(amount) {
+                1 => self.config.loan_tier1_requirements(),
+                2 => self.config.loan_tier2_requirements(),
+                _ => self.config.loan_tier3_requirements(),
+            }
+        }
+
+        /// Internal: Raise a star requirement for borrowers inactive beyond the configured window
+        /// An old qualification is not enough on its own; idle borrowers need proportionally more stars.
+        fn apply_inactivity_surcharge(&self, borrower: AccountId, min_stars: u32) -> u32 {
+            let (window, surcharge_percent) = self.config.get_inactivity_surcharge();
+            if window == 0 {
+                return min_stars;
+            }
+            let last_activity = self.reputation.get_last_activity(borrower);
+            Self::inactivity_surcharge(min_stars, last_activity, self.env().block_timestamp(), window, surcharge_percent)
+        }
+
+        /// Internal: `min_stars` plus `surcharge_percent` of it when `last_activity` is more than
+        /// `window` before `now`
+        /// Users without a reputation record report a last activity of 0 and so are surcharged too:
+        /// intended, as nothing vouches for recent activity of an account that never registered.
+        fn inactivity_surcharge(
+            min_stars: u32,
+            last_activity: Timestamp,
+            now: Timestamp,
+            window: Timestamp,
+            surcharge_percent: u32,
+        ) -> u32 {
+            if now.saturating_sub(last_activity) <= window {
+                return min_stars;
+            }
+            let surcharge = (min_stars as u64)
+                .saturating_mul(surcharge_percent as u64)
+                / 100;
+            (min_stars as u64).saturating_add(surcharge).min(u32::MAX as u64) as u32
+        }
+
+        /// Internal: Multiply a star requirement while pool utilization is above the stress threshold
+        /// Tightens origination when little liquidity is left
+        fn apply_stress_multiplier(&self, min_stars: u32) -> u32 {
+            let (stress_utilization, multiplier_percent) = self.config.get_stress_requirements();
+            if self.lending_pool.get_utilization() <= stress_utilization {
+                return min_stars;
+            }
+            ((min_stars as u64).saturating_mul(multiplier_percent as u64) / 100)
+                .min(u32::MAX as u64) as u32
+        }
+
+        /// Internal: Maximum amount a borrower may request: the ceiling of the highest tier whose
+        /// star minimum their stars meet. Borrowers below every minimum get the first tier's ceiling
+        /// and are then turned away by the star check; a per-borrower override opens every tier.
+        /// The inactivity surcharge and stress multiplier apply in the star check on the amount itself.
+        fn borrower_tier_limit(&self, borrower: AccountId, stars: u32) -> Balance {
+            let tier = if self.borrower_overrides.contains(borrower) {
+                3
+            } else {
+                Self::qualifying_tier(
+                    stars,
+                    self.config.loan_tier2_requirements().0,
+                    self.config.loan_tier3_requirements().0,
+                )
+            };
+            self.calculate_tier_limit(tier)
+        }
+
+        /// Internal: Highest tier whose star minimum `stars` meets; the first tier otherwise
+        fn qualifying_tier(stars: u32, tier2_min_stars: u32, tier3_min_stars: u32) -> u8 {
+            if stars >= tier3_min_stars {
+                3
+            } else if stars >= tier2_min_stars {
+                2
+            } else {
+                1
+            }
+        }
+
+        /// Internal: Maximum amount a single loan may request within `tier`
+        /// The configured ceiling is in scaled units and is converted back to raw units here.
+        fn calculate_tier_limit(&self, tier: u8) -> Balance {
+            let scaled_limit = match tier {
+                1 => self.config.loan_tier1_max_amount(),
+                2 => self.config.loan_tier2_max_amount(),
+                _ => self.config.loan_tier3_max_amount(),
+            };
+            Self::unscaled_limit(scaled_limit, self.config.loan_tier_scaling_factor())
+        }
+
+        /// Internal: A ceiling in scaled units converted back to raw units
+        fn unscaled_limit(scaled_limit: Balance, scaling_factor: Balance) -> Balance {
+            if scaling_factor > 0 {
+                scaled_limit.saturating_mul(scaling_factor)
+            } else {
+                scaled_limit
+            }
+        }
+
+        /// Internal: Rate a new loan is originated at
+        /// Star discount on the pool rate, plus the term premium for each full premium period
+        /// of the term, capped at max_rate
+        fn origination_rate(&self, base_rate: u64, stars: u32, term: Timestamp) -> u64 {
+            let (premium_per_period, period) = self.config.get_term_premium();
+            let premium = if period > 0 {
+                (term / period).saturating_mul(premium_per_period)
+            } else {
+                0
+            };
+            self.adjust_rate_by_stars(base_rate, stars)
+                .saturating_add(premium)
+                .min(self.config.get_max_rate())
+        }
+
+        /// Internal: Stars to slash from a defaulting borrower
+        /// Proportional to the loan amount (using consistent token decimals), raised by
+        /// term_penalty_factor percent for every full period the loan ran, term plus lateness.
+        /// Never less than one star.
+        fn default_star_slash(&self, loan: &Loan, now: Timestamp) -> u32 {
+            let amount_scale = self.config.get_amount_scale().max(1);
+            let base = (loan.amount / amount_scale) as u64;
+
+            let (factor, period) = self.config.get_term_penalty();
+            let periods = if period > 0 {
+                now.saturating_sub(loan.start_time) / period
+            } else {
+                0
+            };
+            let extra = base
+                .saturating_mul(periods)
+                .saturating_mul(factor as u64)
+                / 100;
+            base.saturating_add(extra).max(1).min(u32::MAX as u64) as u32
+        }
+
         /// Internal: Adjust interest rate based on borrower's stars
         /// Higher stars result in lower interest rates
         /// Uses configurable parameters from Config contract
@@ -472,20 +1481,177 @@ mod loan_manager {
             base_rate.saturating_sub(discount)
         }
 
+        /// Internal: Total repayment (principal + interest) for a loan at its own interest rate
+        /// Always called with the loan's snapshotted `interest_rate`, never the pool's current
+        /// rate, so later pool rate changes don't alter what the borrower owes.
+        /// Repayment = amount * (1 + interest_rate_percentage)
+        /// The interest_rate is stored as percentage * rate_scale (1e9, e.g., 10% = 10_000_000_000)
+        /// So 100% is rate_scale * 100 (1e11) and: repayment = amount * (1 + interest_rate / (rate_scale * 100))
+        /// Example: 100 tokens at 10% = 100 * (1 + 10_000_000_000 / 100_000_000_000) = 100 * 1.10 = 110
+        fn calculate_total_repayment(&self, amount: Balance, interest_rate: u64) -> Balance {
+            Self::total_repayment(amount, interest_rate, self.config.get_rate_scale())
+        }
+
+        /// Internal: Principal plus interest at a rate expressed in percent x rate_scale
+        fn total_repayment(amount: Balance, interest_rate: u64, rate_scale: u64) -> Balance {
+            let hundred_percent = (rate_scale as u128).saturating_mul(100);
+            // Owed by the borrower, so any fractional unit rounds up
+            mul_div(amount as u128, hundred_percent + interest_rate as u128, hundred_percent, Rounding::Up)
+                .unwrap_or(amount) as Balance
+        }
+
         /// Internal: Calculate repayment amount (principal + interest)
-        /// Returns the fixed repayment amount calculated at loan creation
+        /// Returns the part of the fixed repayment amount that has not been repaid yet
         fn calculate_repayment_amount(&self, loan: &Loan) -> Balance {
-            loan.total_repayment_amount
+            loan.total_repayment_amount.saturating_sub(loan.repaid_amount)
+        }
+
+        /// Internal: Amount a full repayment costs right now (10 decimals)
+        fn calculate_amount_owed(&self, loan: &Loan) -> Balance {
+            let elapsed = self.env().block_timestamp().saturating_sub(loan.start_time);
+            self.amount_owed_at(loan, elapsed, self.config.get_interest_free_period())
+        }
+
+        /// Internal: Amount a full repayment of an active loan costs `elapsed` ms after disbursement
+        /// The outstanding principal plus the unpaid share of the interest accrued so far, see
+        /// `accrued_interest`. Loans no longer active owe the remaining repayment amount in full.
+        fn amount_owed_at(&self, loan: &Loan, elapsed: Timestamp, interest_free_period: Timestamp) -> Balance {
+            let remaining = self.calculate_repayment_amount(loan);
+            if loan.status != LoanStatus::Active || loan.total_repayment_amount == 0 {
+                return remaining;
+            }
+            let accrued = Self::accrued_interest(loan, elapsed, interest_free_period);
+            if accrued == 0 {
+                return self.calculate_outstanding_principal(loan);
+            }
+            // Repayments cover principal and interest pro rata, so the unpaid share of both is owed
+            // Owed by the borrower, so any fractional unit rounds up
+            mul_div(
+                remaining as u128,
+                (loan.amount as u128).saturating_add(accrued as u128),
+                loan.total_repayment_amount as u128,
+                Rounding::Up,
+            )
+            .map_or(remaining, |owed| (owed as Balance).min(remaining))
+        }
+
+        /// Internal: (principal, interest) split of `amount_owed_at` (10 decimals)
+        fn breakdown_at(&self, loan: &Loan, elapsed: Timestamp, interest_free_period: Timestamp) -> (Balance, Balance) {
+            let owed = self.amount_owed_at(loan, elapsed, interest_free_period);
+            let principal = self.calculate_outstanding_principal(loan).min(owed);
+            (principal, owed.saturating_sub(principal))
+        }
+
+        /// Internal: Part of the loan's fixed interest accrued `elapsed` ms after disbursement
+        /// Nothing accrues inside the interest-free window; afterwards the interest accrues linearly,
+        /// (elapsed - interest_free_period) / term of it, and never exceeds the full interest
+        fn accrued_interest(loan: &Loan, elapsed: Timestamp, interest_free_period: Timestamp) -> Balance {
+            let interest = loan.total_repayment_amount.saturating_sub(loan.amount);
+            let accruing = elapsed.saturating_sub(interest_free_period);
+            if accruing >= loan.term {
+                return interest;
+            }
+            mul_div(interest as u128, accruing as u128, loan.term as u128, Rounding::Up)
+                .map_or(interest, |accrued| (accrued as Balance).min(interest))
+        }
+
+        /// Internal: Principal not yet repaid (10 decimals)
+        /// Repayments cover principal and interest pro rata, so the unpaid share of the
+        /// repayment amount is applied to the principal
+        fn calculate_outstanding_principal(&self, loan: &Loan) -> Balance {
+            if loan.total_repayment_amount == 0 {
+                return loan.amount;
+            }
+            (loan.amount as u128)
+                .checked_mul(self.calculate_repayment_amount(loan) as u128)
+                .and_then(|v| v.checked_div(loan.total_repayment_amount as u128))
+                .unwrap_or(0) as Balance
+        }
+
+        /// Internal: Calculate the amount due for the next installment (10 decimals)
+        /// The final installment covers the remaining balance, including rounding dust
+        fn calculate_installment_amount(&self, loan: &Loan) -> Balance {
+            let remaining_installments = loan.num_installments.saturating_sub(loan.installments_paid);
+            if remaining_installments <= 1 {
+                return self.calculate_repayment_amount(loan);
+            }
+            loan.total_repayment_amount / loan.num_installments as Balance
+        }
+
+        /// Internal: Due time of the next unpaid installment
+        /// Installments are spread evenly over the term; the last one is due at start_time + term
+        fn next_due_time(&self, loan: &Loan) -> Timestamp {
+            let num_installments = loan.num_installments.max(1) as Timestamp;
+            let next_installment = (loan.installments_paid as Timestamp)
+                .saturating_add(1)
+                .min(num_installments);
+            let elapsed = loan.term
+                .saturating_mul(next_installment)
+                / num_installments;
+            loan.start_time.saturating_add(elapsed)
+        }
+
+        /// Internal: Stars rewarded for repaying a loan
+        /// reward = base + amount / amount_divisor + term / term_divisor, capped at the configured cap
+        fn calculate_repay_reward(&self, loan: &Loan) -> u32 {
+            let (base, amount_divisor, term_divisor, cap) = self.config.get_repay_reward_curve();
+            let amount_stars = if amount_divisor > 0 { loan.amount / amount_divisor } else { 0 };
+            let term_stars = if term_divisor > 0 { loan.term / term_divisor } else { 0 };
+            let reward = (base as u128)
+                .saturating_add(amount_stars)
+                .saturating_add(term_stars as u128);
+            reward.min(cap as u128) as u32
+        }
+
+        /// Internal: Calculate the extension fee for a loan (10 decimals)
+        fn calculate_extension_fee(&self, loan: &Loan) -> Balance {
+            let fee_percent = self.config.get_extension_fee_percent();
+            (loan.amount as u128)
+                .checked_mul(fee_percent as u128)
+                .and_then(|v| v.checked_div(100))
+                .unwrap_or(0) as Balance
         }
 
         /// Get the repayment amount for a loan
-        /// Returns the fixed repayment amount (principal + interest) calculated at loan creation
+        /// Returns the outstanding principal plus the interest accrued so far, i.e. the exact value to
+        /// send with `repay_loan`. Inside the interest-free window this is the outstanding principal only;
+        /// after the term (or once the loan is overdue) it is the full repayment amount
         /// Returns value in 18 decimals (chain format) for consistency with total liquidity
         #[ink(message)]
         pub fn get_repayment_amount(&self, loan_id: u64) -> Result<Balance> {
             let loan = self.loans.get(loan_id).ok_or(Error::LoanNotFound)?;
             // Convert from 10 decimals (storage) to 18 decimals (chain format)
-            Ok(self.convert_10_to_18_decimals(loan.total_repayment_amount))
+            Ok(self.convert_10_to_18_decimals(self.calculate_amount_owed(&loan)))
+        }
+
+        /// Split the amount still owed on a loan into (principal, interest)
+        /// Interest is what has accrued by now, so it is 0 inside the interest-free window and grows
+        /// with elapsed time up to the loan's full interest; both parts are in 18 decimals and sum to
+        /// `get_repayment_amount`
+        #[ink(message)]
+        pub fn owed_breakdown(&self, loan_id: u64) -> Option<(Balance, Balance)> {
+            let loan = self.loans.get(loan_id)?;
+            let elapsed = self.env().block_timestamp().saturating_sub(loan.start_time);
+            let (principal, interest) = self.breakdown_at(&loan, elapsed, self.config.get_interest_free_period());
+            Some((
+                self.convert_10_to_18_decimals(principal),
+                self.convert_10_to_18_decimals(interest),
+            ))
+        }
+
+        /// Get the amount due for the next installment of a loan
+        /// Returns value in 18 decimals (chain format), the exact value to send with `repay_installment`
+        #[ink(message)]
+        pub fn get_installment_amount(&self, loan_id: u64) -> Result<Balance> {
+            let loan = self.loans.get(loan_id).ok_or(Error::LoanNotFound)?;
+            Ok(self.convert_10_to_18_decimals(self.calculate_installment_amount(&loan)))
+        }
+
+        /// Get the due time of the next unpaid installment of a loan
+        #[ink(message)]
+        pub fn get_next_due_time(&self, loan_id: u64) -> Result<Timestamp> {
+            let loan = self.loans.get(loan_id).ok_or(Error::LoanNotFound)?;
+            Ok(self.next_due_time(&loan))
         }
 
         /// Get all pending loans
@@ -514,12 +1680,967 @@ mod loan_manager {
             let max_id = self.next_loan_id;
             for loan_id in 1..max_id {
                 if let Some(loan) = self.loans.get(loan_id) {
-                    if loan.status == LoanStatus::Active {
+                    if loan.status.is_outstanding() {
                         active_loans.push(loan_id);
                     }
                 }
             }
             active_loans
-        }        
+        }
+
+        /// Get a borrower's latest Pending loan as (loan_id, borrower account), looked up by address
+        /// Lets the vouch contract attach vouches to borrowers it only knows by address
+        #[ink(message)]
+        pub fn get_pending_loan(&self, borrower: Address) -> Option<(u64, AccountId)> {
+            (1..self.next_loan_id)
+                .rev()
+                .filter_map(|loan_id| self.loans.get(loan_id))
+                .find(|loan| loan.status == LoanStatus::Pending
+                    && AccountIdMapper::to_address(loan.borrower.as_ref()) == borrower)
+                .map(|loan| (loan.loan_id, loan.borrower))
+        }
+
+        /// Page through the ids of currently active loans, e.g. for keepers scheduling default checks
+        /// Returns up to `limit` ids starting at position `start`; the order is not stable
+        /// across status changes, since removals move the last id into the freed slot
+        #[ink(message)]
+        pub fn get_active_loans_paged(&self, start: u32, limit: u32) -> Vec<u64> {
+            let start = start as u64;
+            let end = start.saturating_add(limit as u64).min(self.active_loan_count);
+            (start..end)
+                .filter_map(|position| self.active_loan_ids.get(position))
+                .collect()
+        }
+
+        /// Get the number of currently active loans without scanning loan ids
+        #[ink(message)]
+        pub fn get_active_loan_count(&self) -> u64 {
+            self.active_loan_count
+        }
+
+        /// Sum of the principal (`amount`, 10 decimals) of every Active or PendingDefault loan, from the active-loan index
+        /// Cross-check against the pool's total_borrowed (18 decimals) to spot accounting drift
+        #[ink(message)]
+        pub fn total_outstanding_principal(&self) -> Balance {
+            (0..self.active_loan_count)
+                .filter_map(|position| self.active_loan_ids.get(position))
+                .filter_map(|loan_id| self.loans.get(loan_id))
+                .fold(0, |total: Balance, loan| total.saturating_add(loan.amount))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const BORROWER: [u8; 32] = [0x02; 32];
+
+        fn borrower() -> AccountId {
+            AccountId::from(BORROWER)
+        }
+
+        fn new_loan_manager() -> LoanManager {
+            LoanManager::new(
+                Address::from([0x10; 20]),
+                Address::from([0x11; 20]),
+                Address::from([0x12; 20]),
+                Address::from([0x13; 20]),
+            )
+        }
+
+        fn loan(loan_id: u64, borrower: AccountId, amount: Balance, status: LoanStatus) -> Loan {
+            Loan {
+                loan_id,
+                interest_rate: 0,
+                term: 1_000,
+                start_time: 0,
+                amount,
+                borrower,
+                status,
+                total_repayment_amount: amount,
+                extensions: 0,
+                num_installments: 1,
+                installments_paid: 0,
+                repaid_amount: 0,
+                vouchers: Vec::new(),
+            }
+        }
+
+        /// Store a loan the way request_loan and the status transitions leave it
+        fn insert_loan(loan_manager: &mut LoanManager, loan: Loan) {
+            let mut loans = loan_manager.borrower_loans.get(loan.borrower).unwrap_or_default();
+            loans.push(loan.loan_id);
+            loan_manager.borrower_loans.insert(loan.borrower, &loans);
+            loan_manager.next_loan_id = loan_manager.next_loan_id.max(loan.loan_id + 1);
+            if loan.status.is_outstanding() {
+                loan_manager.add_active_loan(loan.loan_id);
+            }
+            loan_manager.loans.insert(loan.loan_id, &loan);
+        }
+
+        #[ink::test]
+        fn disburse_refusals_keep_their_reason() {
+            assert_eq!(
+                LoanManager::disburse_refusal(LendingPoolError::UnavailableFunds),
+                Error::InsufficientLiquidity
+            );
+            assert_eq!(LoanManager::disburse_refusal(LendingPoolError::BufferViolation), Error::BufferViolation);
+            assert_eq!(
+                LoanManager::disburse_refusal(LendingPoolError::GlobalCapExceeded),
+                Error::GlobalCapExceeded
+            );
+        }
+
+        /// Active 1_000 loan at 10% interest over a 1_000 ms term
+        fn interest_loan() -> Loan {
+            let mut interest_loan = loan(1, borrower(), 1_000, LoanStatus::Active);
+            interest_loan.total_repayment_amount = 1_100;
+            interest_loan
+        }
+
+        #[ink::test]
+        fn interest_accrues_pro_rata_after_the_interest_free_window() {
+            let loan_manager = new_loan_manager();
+            let active = interest_loan();
+            let free = 100;
+
+            // Inside the window only the principal is owed
+            assert_eq!(loan_manager.amount_owed_at(&active, 0, free), 1_000);
+            assert_eq!(loan_manager.amount_owed_at(&active, 100, free), 1_000);
+            // Afterwards (elapsed - window) / term of the interest is added
+            assert_eq!(loan_manager.amount_owed_at(&active, 101, free), 1_001);
+            assert_eq!(loan_manager.amount_owed_at(&active, 600, free), 1_050);
+            // Capped at the full interest once the term has run past the window
+            assert_eq!(loan_manager.amount_owed_at(&active, 1_100, free), 1_100);
+            assert_eq!(loan_manager.amount_owed_at(&active, 5_000, free), 1_100);
+            // Without a window interest accrues from disbursement
+            assert_eq!(loan_manager.amount_owed_at(&active, 500, 0), 1_050);
+
+            // Loans marked overdue owe the full repayment amount
+            let mut overdue = interest_loan();
+            overdue.status = LoanStatus::PendingDefault;
+            assert_eq!(loan_manager.amount_owed_at(&overdue, 100, free), 1_100);
+        }
+
+        #[ink::test]
+        fn owed_breakdown_splits_by_elapsed_time() {
+            let loan_manager = new_loan_manager();
+            let free = 100;
+            let mut active = interest_loan();
+
+            for (elapsed, expected) in [(0, (1_000, 0)), (100, (1_000, 0)), (350, (1_000, 25)), (600, (1_000, 50)), (2_000, (1_000, 100))] {
+                let (principal, interest) = loan_manager.breakdown_at(&active, elapsed, free);
+                assert_eq!((principal, interest), expected, "elapsed {elapsed}");
+                assert_eq!(principal + interest, loan_manager.amount_owed_at(&active, elapsed, free));
+            }
+
+            // After half the repayment came in, half of each part is left
+            active.repaid_amount = 550;
+            for (elapsed, expected) in [(0, (500, 0)), (600, (500, 25)), (2_000, (500, 50))] {
+                let (principal, interest) = loan_manager.breakdown_at(&active, elapsed, free);
+                assert_eq!((principal, interest), expected, "elapsed {elapsed}");
+                assert_eq!(principal + interest, loan_manager.amount_owed_at(&active, elapsed, free));
+            }
+        }
+
+        #[ink::test]
+        fn scaling_factor_moves_the_tier_boundaries() {
+            // Default thresholds: 1000 / 10000 scaled units
+            let factor = 1_000_000_000;
+            assert_eq!(LoanManager::tier_for(999 * factor, factor, 1000, 10000), 1);
+            assert_eq!(LoanManager::tier_for(1000 * factor, factor, 1000, 10000), 2);
+            assert_eq!(LoanManager::tier_for(10000 * factor, factor, 1000, 10000), 3);
+            // The same amount lands in a higher tier once the factor shrinks
+            assert_eq!(LoanManager::tier_for(999 * factor, factor / 10, 1000, 10000), 2);
+            // A zero factor compares the raw amount
+            assert_eq!(LoanManager::tier_for(999, 0, 1000, 10000), 1);
+        }
+
+        #[ink::test]
+        fn rate_scale_drives_the_total_repayment() {
+            // 10% interest on 1_000 is 1_100 whatever the scale, as long as the rate uses it
+            assert_eq!(LoanManager::total_repayment(1_000, 10_000_000_000, 1_000_000_000), 1_100);
+            assert_eq!(LoanManager::total_repayment(1_000, 10_000_000, 1_000_000), 1_100);
+            // A rate on the wrong scale is read as a different percentage
+            assert_eq!(LoanManager::total_repayment(1_000, 10_000_000, 1_000_000_000), 1_001);
+            // Fractional units are owed by the borrower, so they round up
+            assert_eq!(LoanManager::total_repayment(3, 10_000_000, 1_000_000), 4);
+        }
+
+        #[ink::test]
+        fn installments_split_the_repayment_evenly_over_the_term() {
+            let mut loan_manager = new_loan_manager();
+            let mut installment_loan = loan(1, borrower(), 1_000, LoanStatus::Active);
+            installment_loan.total_repayment_amount = 1_001;
+            installment_loan.num_installments = 4;
+            installment_loan.term = 4_000;
+            insert_loan(&mut loan_manager, installment_loan);
+
+            assert_eq!(loan_manager.get_installment_amount(1), Ok(250 * 100_000_000));
+            assert_eq!(loan_manager.get_next_due_time(1), Ok(1_000));
+
+            // The final installment settles the rounding dust
+            let mut last = loan_manager.get_loan(1).unwrap();
+            last.installments_paid = 3;
+            last.repaid_amount = 750;
+            loan_manager.loans.insert(1, &last);
+            assert_eq!(loan_manager.get_installment_amount(1), Ok(251 * 100_000_000));
+            assert_eq!(loan_manager.get_next_due_time(1), Ok(4_000));
+        }
+
+        #[ink::test]
+        fn transitions_keep_the_active_index_in_step() {
+            let mut loan_manager = new_loan_manager();
+            let mut pending = loan(1, borrower(), 100, LoanStatus::Pending);
+            insert_loan(&mut loan_manager, loan(1, borrower(), 100, LoanStatus::Pending));
+
+            loan_manager.transition(&mut pending, LoanStatus::Active);
+            assert_eq!(loan_manager.get_active_loan_count(), 1);
+            // A loan in its appeal window is still outstanding
+            loan_manager.transition(&mut pending, LoanStatus::PendingDefault);
+            assert_eq!(loan_manager.get_active_loan_count(), 1);
+            loan_manager.transition(&mut pending, LoanStatus::Defaulted);
+            assert_eq!(loan_manager.get_active_loan_count(), 0);
+            assert_eq!(pending.status, LoanStatus::Defaulted);
+        }
+
+        #[ink::test]
+        fn loan_vouchers_are_read_from_the_loan() {
+            let mut loan_manager = new_loan_manager();
+            let mut backed = loan(1, borrower(), 100, LoanStatus::Active);
+            backed.vouchers = vec![AccountId::from([0x03; 32]), AccountId::from([0x04; 32])];
+            insert_loan(&mut loan_manager, backed);
+
+            assert_eq!(loan_manager.get_loan_vouchers(1), vec![AccountId::from([0x03; 32]), AccountId::from([0x04; 32])]);
+            assert_eq!(loan_manager.get_loan_vouchers(2), Vec::<AccountId>::new());
+        }
+
+        #[ink::test]
+        fn pending_loan_lookup_finds_the_latest_pending_loan_by_address() {
+            let mut loan_manager = new_loan_manager();
+            insert_loan(&mut loan_manager, loan(1, borrower(), 100, LoanStatus::Pending));
+            insert_loan(&mut loan_manager, loan(2, borrower(), 200, LoanStatus::Pending));
+            insert_loan(&mut loan_manager, loan(3, borrower(), 300, LoanStatus::Active));
+            insert_loan(&mut loan_manager, loan(4, AccountId::from([0x03; 32]), 400, LoanStatus::Pending));
+
+            let address = AccountIdMapper::to_address(&BORROWER);
+            assert_eq!(loan_manager.get_pending_loan(address), Some((2, borrower())));
+            assert_eq!(loan_manager.get_pending_loan(Address::from([0x20; 20])), None);
+        }
+
+        #[ink::test]
+        fn active_loan_count_ignores_pending_and_closed_loans() {
+            let mut loan_manager = new_loan_manager();
+            insert_loan(&mut loan_manager, loan(1, borrower(), 100, LoanStatus::Pending));
+            insert_loan(&mut loan_manager, loan(2, borrower(), 100, LoanStatus::Active));
+            insert_loan(&mut loan_manager, loan(3, borrower(), 100, LoanStatus::PendingDefault));
+            insert_loan(&mut loan_manager, loan(4, borrower(), 100, LoanStatus::Repaid));
+            assert_eq!(loan_manager.get_active_loan_count(), 2);
+            assert_eq!(loan_manager.get_all_active_loans(), vec![2, 3]);
+        }
+
+        #[ink::test]
+        fn largest_active_loan_skips_closed_loans_and_keeps_the_oldest_tie() {
+            let mut loan_manager = new_loan_manager();
+            assert_eq!(loan_manager.largest_active_loan(borrower()), None);
+            insert_loan(&mut loan_manager, loan(1, borrower(), 500, LoanStatus::Repaid));
+            insert_loan(&mut loan_manager, loan(2, borrower(), 300, LoanStatus::Active));
+            insert_loan(&mut loan_manager, loan(3, borrower(), 300, LoanStatus::PendingDefault));
+            insert_loan(&mut loan_manager, loan(4, borrower(), 900, LoanStatus::Pending));
+            assert_eq!(loan_manager.largest_active_loan(borrower()), Some((2, 300)));
+        }
+
+        #[ink::test]
+        fn active_loans_page_through_the_index() {
+            let mut loan_manager = new_loan_manager();
+            for loan_id in 1..=5 {
+                insert_loan(&mut loan_manager, loan(loan_id, borrower(), 100, LoanStatus::Active));
+            }
+            assert_eq!(loan_manager.get_active_loans_paged(0, 2), vec![1, 2]);
+            assert_eq!(loan_manager.get_active_loans_paged(4, 10), vec![5]);
+            assert_eq!(loan_manager.get_active_loans_paged(5, 10), Vec::<u64>::new());
+
+            // Removing a loan moves the last id into its slot
+            loan_manager.remove_active_loan(2);
+            assert_eq!(loan_manager.get_active_loans_paged(0, 10), vec![1, 5, 3, 4]);
+        }
+
+        #[ink::test]
+        fn lifetime_stats_accumulate() {
+            let mut loan_manager = new_loan_manager();
+            assert_eq!(loan_manager.get_lifetime_stats(borrower()), (0, 0, 0));
+            loan_manager.update_lifetime_stats(borrower(), 1_000, 0, 0);
+            loan_manager.update_lifetime_stats(borrower(), 0, 1_100, 0);
+            loan_manager.update_lifetime_stats(borrower(), 500, 0, 1);
+            assert_eq!(loan_manager.get_lifetime_stats(borrower()), (1_500, 1_100, 1));
+        }
+
+        #[ink::test]
+        fn disbursement_only_goes_to_the_recorded_borrower() {
+            let loan_manager = new_loan_manager();
+            let pending = loan(1, borrower(), 100, LoanStatus::Pending);
+            assert_eq!(
+                loan_manager.pool_disburse(&pending, AccountId::from([0x03; 32])),
+                Err(Error::InvalidRecipient)
+            );
+        }
+
+        #[ink::test]
+        fn time_to_due_goes_negative_once_overdue() {
+            let mut loan_manager = new_loan_manager();
+            insert_loan(&mut loan_manager, loan(1, borrower(), 100, LoanStatus::Active));
+            insert_loan(&mut loan_manager, loan(2, borrower(), 100, LoanStatus::Pending));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(400);
+            assert_eq!(loan_manager.time_to_due(1), Some(600));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_500);
+            assert_eq!(loan_manager.time_to_due(1), Some(-500));
+            assert_eq!(loan_manager.time_to_due(2), None);
+            assert_eq!(loan_manager.time_to_due(3), None);
+        }
+
+        #[ink::test]
+        fn outstanding_principal_sums_the_active_index() {
+            let mut loan_manager = new_loan_manager();
+            insert_loan(&mut loan_manager, loan(1, borrower(), 100, LoanStatus::Active));
+            insert_loan(&mut loan_manager, loan(2, borrower(), 250, LoanStatus::PendingDefault));
+            insert_loan(&mut loan_manager, loan(3, borrower(), 1_000, LoanStatus::Pending));
+            assert_eq!(loan_manager.total_outstanding_principal(), 350);
+        }
+
+
+        #[ink::test]
+        fn stars_pick_the_tier_ceiling() {
+            // Default minimums: 20 stars for tier 2, 50 for tier 3
+            assert_eq!(LoanManager::qualifying_tier(0, 20, 50), 1);
+            assert_eq!(LoanManager::qualifying_tier(19, 20, 50), 1);
+            assert_eq!(LoanManager::qualifying_tier(20, 20, 50), 2);
+            assert_eq!(LoanManager::qualifying_tier(49, 20, 50), 2);
+            assert_eq!(LoanManager::qualifying_tier(50, 20, 50), 3);
+
+            assert_eq!(LoanManager::unscaled_limit(1_000, 1_000_000_000), 1_000_000_000_000);
+            assert_eq!(LoanManager::unscaled_limit(1_000, 0), 1_000);
+        }
+
+        #[ink::test]
+        fn finalize_default_waits_out_the_appeal_window() {
+            let mut loan_manager = new_loan_manager();
+            insert_loan(&mut loan_manager, loan(1, borrower(), 100, LoanStatus::PendingDefault));
+            loan_manager.pending_default_since.insert(1, &1_000);
+            let pending = loan_manager.loans.get(1).unwrap();
+            let appeal_window = 500;
+
+            assert_eq!(loan_manager.check_finalizable(&pending, 1_000, appeal_window), Err(Error::AppealWindowActive));
+            assert_eq!(loan_manager.check_finalizable(&pending, 1_499, appeal_window), Err(Error::AppealWindowActive));
+            assert_eq!(loan_manager.check_finalizable(&pending, 1_500, appeal_window), Ok(()));
+
+            let active = loan(2, borrower(), 100, LoanStatus::Active);
+            assert_eq!(loan_manager.check_finalizable(&active, 5_000, appeal_window), Err(Error::LoanNotPendingDefault));
+        }
+
+        #[ink::test]
+        fn cancel_default_returns_the_loan_to_active() {
+            let mut loan_manager = new_loan_manager();
+            insert_loan(&mut loan_manager, loan(1, borrower(), 100, LoanStatus::PendingDefault));
+            loan_manager.pending_default_since.insert(1, &1_000);
+            let admin = AccountId::from([0x01; 32]);
+
+            assert_eq!(loan_manager.apply_cancel_default(1, admin), Ok(()));
+            assert_eq!(loan_manager.loans.get(1).unwrap().status, LoanStatus::Active);
+            assert_eq!(loan_manager.get_pending_default_since(1), None);
+            assert_eq!(loan_manager.get_active_loan_count(), 1);
+
+            // Only a PendingDefault loan can be cancelled
+            assert_eq!(loan_manager.apply_cancel_default(1, admin), Err(Error::LoanNotPendingDefault));
+            assert_eq!(loan_manager.apply_cancel_default(9, admin), Err(Error::LoanNotFound));
+        }
+
+        #[ink::test]
+        fn repaying_cures_a_pending_default() {
+            let mut loan_manager = new_loan_manager();
+            insert_loan(&mut loan_manager, loan(1, borrower(), 100, LoanStatus::PendingDefault));
+            loan_manager.pending_default_since.insert(1, &1_000);
+            let mut pending = loan_manager.loans.get(1).unwrap();
+
+            // Outstanding loans are repayable, settled ones are not
+            assert_eq!(LoanManager::ensure_repayable(&pending), Ok(()));
+            assert_eq!(LoanManager::ensure_repayable(&loan(2, borrower(), 100, LoanStatus::Active)), Ok(()));
+            assert_eq!(LoanManager::ensure_repayable(&loan(3, borrower(), 100, LoanStatus::Defaulted)), Err(Error::LoanNotActive));
+            assert_eq!(LoanManager::ensure_repayable(&loan(4, borrower(), 100, LoanStatus::Repaid)), Err(Error::LoanNotActive));
+
+            loan_manager.cure_pending_default(&mut pending);
+            assert_eq!(pending.status, LoanStatus::Active);
+            assert_eq!(loan_manager.get_pending_default_since(1), None);
+            // Still outstanding, so still counted once
+            assert_eq!(loan_manager.get_active_loan_count(), 1);
+        }
+
+        #[ink::test]
+        fn inactive_borrowers_need_more_stars_than_active_ones() {
+            // Same 20-star requirement, 180-day window, 50% surcharge
+            let window = 15_552_000_000;
+            let now = 20_000_000_000;
+            let active = LoanManager::inactivity_surcharge(20, now - 1_000, now, window, 50);
+            let inactive = LoanManager::inactivity_surcharge(20, now - window - 1, now, window, 50);
+            assert_eq!(active, 20);
+            assert_eq!(inactive, 30);
+            // Exactly at the window edge still counts as active
+            assert_eq!(LoanManager::inactivity_surcharge(20, now - window, now, window, 50), 20);
+            // An unregistered user reports no activity and is surcharged
+            assert_eq!(LoanManager::inactivity_surcharge(20, 0, now, window, 50), 30);
+        }
+
+        #[ink::test]
+        fn loan_repaid_splits_what_was_paid() {
+            let loan_manager = new_loan_manager();
+            let active = interest_loan();
+
+            // A full repayment halfway through the accruing period: 1_000 principal + 50 interest
+            let (principal, interest) = loan_manager.breakdown_at(&active, 600, 100);
+            let event = LoanManager::loan_repaid_event(&active, principal + interest, principal);
+            assert_eq!((event.amount, event.principal_paid, event.interest_paid), (1_050, 1_000, 50));
+
+            // Inside the interest-free window the whole payment is principal
+            let (principal, interest) = loan_manager.breakdown_at(&active, 50, 100);
+            let event = LoanManager::loan_repaid_event(&active, principal + interest, principal);
+            assert_eq!((event.amount, event.principal_paid, event.interest_paid), (1_000, 1_000, 0));
+
+            // After the final installment the event covers every installment: the fixed interest
+            let mut installments = interest_loan();
+            installments.repaid_amount = 1_100;
+            let principal_paid = installments.amount.min(installments.repaid_amount);
+            let event = LoanManager::loan_repaid_event(&installments, installments.repaid_amount, principal_paid);
+            assert_eq!((event.amount, event.principal_paid, event.interest_paid), (1_100, 1_000, 100));
+            assert_eq!(event.principal_paid + event.interest_paid, event.amount);
+        }
+
+        #[ink::test]
+        fn borrower_profile_counts_only_outstanding_loans() {
+            let mut loan_manager = new_loan_manager();
+            assert_eq!(loan_manager.outstanding_loans(borrower()), (0, 0));
+
+            // Half of 1_100 repaid leaves half of the 1_000 principal
+            let mut half_repaid = interest_loan();
+            half_repaid.repaid_amount = 550;
+            insert_loan(&mut loan_manager, half_repaid);
+            insert_loan(&mut loan_manager, loan(2, borrower(), 2_000, LoanStatus::PendingDefault));
+            insert_loan(&mut loan_manager, loan(3, borrower(), 4_000, LoanStatus::Pending));
+            insert_loan(&mut loan_manager, loan(4, borrower(), 8_000, LoanStatus::Repaid));
+            insert_loan(&mut loan_manager, loan(5, borrower(), 16_000, LoanStatus::Defaulted));
+            insert_loan(&mut loan_manager, loan(6, AccountId::from([0x03; 32]), 32_000, LoanStatus::Active));
+
+            assert_eq!(loan_manager.outstanding_loans(borrower()), (2, 2_500));
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink::codegen::TraitCallBuilder;
+        use ink::env::call::FromAddr;
+        use ink_e2e::{ContractsBackend, E2EBackend};
+        use vouch::VouchError;
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        /// Default tier ceilings (1_000 / 10_000 / 100_000) times the 1e9 scaling factor
+        const TIER_LIMITS: [Balance; 3] = [1_000_000_000_000, 10_000_000_000_000, 100_000_000_000_000];
+        const TERM: Timestamp = 2_592_000_000;
+
+        /// A deployed and wired protocol on default Config values, administered by Alice
+        struct Protocol {
+            config: Address,
+            reputation: Address,
+            lending_pool: Address,
+            vouch: Address,
+            loan_manager: Address,
+        }
+
+        fn account_of(keypair: &ink_e2e::Keypair) -> AccountId {
+            AccountId::from(keypair.public_key().0)
+        }
+
+        async fn deploy<Client: E2EBackend>(client: &mut Client) -> Protocol {
+            let admin = ink_e2e::alice();
+            let config = client
+                .instantiate("config", &admin, &mut ConfigRef::new(account_of(&admin)))
+                .submit()
+                .await
+                .expect("config instantiate failed")
+                .addr;
+            let reputation = client
+                .instantiate("reputation", &admin, &mut ReputationRef::new(config, account_of(&admin)))
+                .submit()
+                .await
+                .expect("reputation instantiate failed")
+                .addr;
+            let lending_pool = client
+                .instantiate("lending_pool", &admin, &mut LendingPoolRef::new(config, 0))
+                .submit()
+                .await
+                .expect("lending_pool instantiate failed")
+                .addr;
+            let vouch = client
+                .instantiate("vouch", &admin, &mut VouchRef::new(config, reputation, lending_pool))
+                .submit()
+                .await
+                .expect("vouch instantiate failed")
+                .addr;
+            let loan_manager = client
+                .instantiate(
+                    "loan_manager",
+                    &admin,
+                    &mut LoanManagerRef::new(config, reputation, lending_pool, vouch),
+                )
+                .submit()
+                .await
+                .expect("loan_manager instantiate failed")
+                .addr;
+
+            let mut pool_ref = LendingPoolRef::from_addr(lending_pool);
+            let mut reputation_ref = ReputationRef::from_addr(reputation);
+            let mut vouch_ref = VouchRef::from_addr(vouch);
+            client.call(&admin, &pool_ref.call_mut().set_loan_manager(loan_manager)).submit().await
+                .expect("pool set_loan_manager failed");
+            client.call(&admin, &pool_ref.call_mut().set_vouch_contract(vouch)).submit().await
+                .expect("pool set_vouch_contract failed");
+            client.call(&admin, &reputation_ref.call_mut().set_loan_manager(loan_manager)).submit().await
+                .expect("reputation set_loan_manager failed");
+            client.call(&admin, &reputation_ref.call_mut().set_vouch_contract(vouch)).submit().await
+                .expect("reputation set_vouch_contract failed");
+            client.call(&admin, &vouch_ref.call_mut().set_loan_manager(loan_manager)).submit().await
+                .expect("vouch set_loan_manager failed");
+
+            Protocol { config, reputation, lending_pool, vouch, loan_manager }
+        }
+
+        /// Give `user` a reputation record with exactly `stars`
+        async fn set_stars<Client: E2EBackend>(client: &mut Client, protocol: &Protocol, user: AccountId, stars: u32) {
+            let mut reputation_ref = ReputationRef::from_addr(protocol.reputation);
+            client.call(&ink_e2e::alice(), &reputation_ref.call_mut().admin_set_stars(user, stars)).submit().await
+                .expect("admin_set_stars failed");
+        }
+
+        /// Outcome of `request_loan` for `amount`, without committing it
+        async fn try_request<Client: E2EBackend>(
+            client: &mut Client,
+            protocol: &Protocol,
+            borrower: AccountId,
+            amount: Balance,
+        ) -> Result<u64> {
+            let mut loan_manager_ref = LoanManagerRef::from_addr(protocol.loan_manager);
+            client
+                .call(&ink_e2e::alice(), &loan_manager_ref.call_mut().request_loan(amount, TERM, 1, borrower))
+                .dry_run()
+                .await
+                .expect("request_loan dry run failed")
+                .return_value()
+        }
+
+        #[ink_e2e::test]
+        async fn request_loan_is_capped_by_the_tier_the_stars_qualify_for<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let protocol = deploy(&mut client).await;
+
+            // 5, 20 and 50 stars meet the tier 1, 2 and 3 minimums
+            for (index, stars) in [5u32, 20, 50].into_iter().enumerate() {
+                let borrower = AccountId::from([0x40 + index as u8; 32]);
+                set_stars(&mut client, &protocol, borrower, stars).await;
+                let limit = TIER_LIMITS[index];
+
+                // Just below the ceiling is within the borrower's own tier
+                assert!(try_request(&mut client, &protocol, borrower, limit - 1).await.is_ok());
+                // The ceiling itself is the next tier's floor, which asks for more stars
+                if index < 2 {
+                    assert_eq!(
+                        try_request(&mut client, &protocol, borrower, limit).await,
+                        Err(Error::InsufficientReputation)
+                    );
+                } else {
+                    assert!(try_request(&mut client, &protocol, borrower, limit).await.is_ok());
+                }
+                // Past it the request is refused outright
+                assert_eq!(
+                    try_request(&mut client, &protocol, borrower, limit + 1).await,
+                    Err(Error::ExceedsTierLimit)
+                );
+            }
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn only_the_admin_can_cancel_a_default<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let protocol = deploy(&mut client).await;
+            let mut loan_manager_ref = LoanManagerRef::from_addr(protocol.loan_manager);
+
+            // Bob is turned away before the loan is even looked up; Alice gets past the admin check
+            let as_bob = client
+                .call(&ink_e2e::bob(), &loan_manager_ref.call_mut().cancel_default(1))
+                .dry_run()
+                .await?;
+            assert_eq!(as_bob.return_value(), Err(Error::Unauthorized));
+            let as_alice = client
+                .call(&ink_e2e::alice(), &loan_manager_ref.call_mut().cancel_default(1))
+                .dry_run()
+                .await?;
+            assert_eq!(as_alice.return_value(), Err(Error::LoanNotFound));
+
+            Ok(())
+        }
+
+        /// Create a pending loan of `amount` for a borrower with `stars`
+        async fn pending_loan<Client: E2EBackend>(
+            client: &mut Client,
+            protocol: &Protocol,
+            borrower: AccountId,
+            stars: u32,
+            amount: Balance,
+        ) -> u64 {
+            set_stars(client, protocol, borrower, stars).await;
+            let mut loan_manager_ref = LoanManagerRef::from_addr(protocol.loan_manager);
+            client
+                .call(&ink_e2e::alice(), &loan_manager_ref.call_mut().request_loan(amount, TERM, 1, borrower))
+                .submit()
+                .await
+                .expect("request_loan failed")
+                .return_value()
+                .expect("request_loan refused")
+        }
+
+        #[ink_e2e::test]
+        async fn admin_resolve_loan_settles_either_way<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let protocol = deploy(&mut client).await;
+            let mut loan_manager_ref = LoanManagerRef::from_addr(protocol.loan_manager);
+            let (repaid_borrower, defaulted_borrower) = (AccountId::from([0x50; 32]), AccountId::from([0x51; 32]));
+            let repaid_id = pending_loan(&mut client, &protocol, repaid_borrower, 5, 1_000).await;
+            let defaulted_id = pending_loan(&mut client, &protocol, defaulted_borrower, 5, 1_000).await;
+
+            // Only the Config admin may force a resolution
+            let as_bob = client
+                .call(&ink_e2e::bob(), &loan_manager_ref.call_mut().admin_resolve_loan(repaid_id, true, protocol.loan_manager))
+                .dry_run()
+                .await?;
+            assert_eq!(as_bob.return_value(), Err(Error::Unauthorized));
+
+            client
+                .call(&ink_e2e::alice(), &loan_manager_ref.call_mut().admin_resolve_loan(repaid_id, true, protocol.loan_manager))
+                .submit()
+                .await
+                .expect("resolving as repaid failed");
+            client
+                .call(&ink_e2e::alice(), &loan_manager_ref.call_mut().admin_resolve_loan(defaulted_id, false, protocol.loan_manager))
+                .submit()
+                .await
+                .expect("resolving as defaulted failed");
+
+            let get_repaid = loan_manager_ref.call_mut().get_loan(repaid_id);
+            let repaid = client.call(&ink_e2e::alice(), &get_repaid).dry_run().await?.return_value();
+            assert_eq!(repaid.map(|loan| loan.status), Some(LoanStatus::Repaid));
+            let get_defaulted = loan_manager_ref.call_mut().get_loan(defaulted_id);
+            let defaulted = client.call(&ink_e2e::alice(), &get_defaulted).dry_run().await?.return_value();
+            assert_eq!(defaulted.map(|loan| loan.status), Some(LoanStatus::Defaulted));
+            // A failed resolution counts as a default in the borrower's lifetime stats
+            let stats = loan_manager_ref.call_mut().get_lifetime_stats(defaulted_borrower);
+            assert_eq!(client.call(&ink_e2e::alice(), &stats).dry_run().await?.return_value().2, 1);
+
+            // Settled loans can't be resolved again
+            let again = client
+                .call(&ink_e2e::alice(), &loan_manager_ref.call_mut().admin_resolve_loan(repaid_id, false, protocol.loan_manager))
+                .dry_run()
+                .await?;
+            assert_eq!(again.return_value(), Err(Error::LoanNotActive));
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn requirements_for_follows_tiers_and_overrides<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let protocol = deploy(&mut client).await;
+            let mut loan_manager_ref = LoanManagerRef::from_addr(protocol.loan_manager);
+            let borrower = AccountId::from([0x60; 32]);
+            // A registered, recently active borrower, so no inactivity surcharge applies
+            set_stars(&mut client, &protocol, borrower, 0).await;
+
+            // Tier defaults (5, 1), (20, 2), (50, 3) on either side of each boundary
+            for (amount, expected) in [
+                (TIER_LIMITS[0] - 1, (5, 1)),
+                (TIER_LIMITS[0], (20, 2)),
+                (TIER_LIMITS[1] - 1, (20, 2)),
+                (TIER_LIMITS[1], (50, 3)),
+            ] {
+                let requirements = loan_manager_ref.call_mut().requirements_for(borrower, amount);
+                assert_eq!(client.call(&ink_e2e::alice(), &requirements).dry_run().await?.return_value(), expected);
+            }
+
+            // An override replaces the tier defaults for that borrower only
+            client
+                .call(&ink_e2e::alice(), &loan_manager_ref.call_mut().set_borrower_override(borrower, 9, 4))
+                .submit()
+                .await
+                .expect("set_borrower_override failed");
+            let overridden = loan_manager_ref.call_mut().requirements_for(borrower, TIER_LIMITS[1]);
+            assert_eq!(client.call(&ink_e2e::alice(), &overridden).dry_run().await?.return_value(), (9, 4));
+            let other = loan_manager_ref.call_mut().requirements_for(AccountId::from([0x61; 32]), TIER_LIMITS[0] - 1);
+            // A borrower without a reputation record keeps the tier defaults, plus the 50% inactivity
+            // surcharge on stars (5 + 2)
+            assert_eq!(client.call(&ink_e2e::alice(), &other).dry_run().await?.return_value(), (7, 1));
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn stars_shortfall_counts_the_missing_stars<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let protocol = deploy(&mut client).await;
+            let mut loan_manager_ref = LoanManagerRef::from_addr(protocol.loan_manager);
+            let borrower = AccountId::from([0x70; 32]);
+            set_stars(&mut client, &protocol, borrower, 3).await;
+
+            // 3 stars against the 5 / 20 / 50 tier minimums
+            for (amount, expected) in [(1_000, 2u32), (TIER_LIMITS[0], 17), (TIER_LIMITS[1], 47)] {
+                let shortfall = loan_manager_ref.call_mut().stars_shortfall(borrower, amount);
+                assert_eq!(client.call(&ink_e2e::alice(), &shortfall).dry_run().await?.return_value(), expected);
+            }
+
+            // Nothing is missing once the borrower qualifies, however far above the minimum
+            set_stars(&mut client, &protocol, borrower, 30).await;
+            let shortfall = loan_manager_ref.call_mut().stars_shortfall(borrower, 1_000);
+            assert_eq!(client.call(&ink_e2e::alice(), &shortfall).dry_run().await?.return_value(), 0);
+            let shortfall = loan_manager_ref.call_mut().stars_shortfall(borrower, TIER_LIMITS[1]);
+            assert_eq!(client.call(&ink_e2e::alice(), &shortfall).dry_run().await?.return_value(), 20);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn vouch_shortfall_counts_the_missing_vouches<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let protocol = deploy(&mut client).await;
+            let mut loan_manager_ref = LoanManagerRef::from_addr(protocol.loan_manager);
+            let borrower = AccountId::from([0x71; 32]);
+
+            // No vouches yet against the 1 / 2 / 3 tier minimums
+            for (amount, expected) in [(1_000, 1u32), (TIER_LIMITS[0], 2), (TIER_LIMITS[1], 3)] {
+                let shortfall = loan_manager_ref.call_mut().vouch_shortfall(borrower, amount);
+                assert_eq!(client.call(&ink_e2e::alice(), &shortfall).dry_run().await?.return_value(), expected);
+            }
+
+            // An override's vouch minimum replaces the tier's, down to needing none
+            client
+                .call(&ink_e2e::alice(), &loan_manager_ref.call_mut().set_borrower_override(borrower, 5, 0))
+                .submit()
+                .await
+                .expect("set_borrower_override failed");
+            let shortfall = loan_manager_ref.call_mut().vouch_shortfall(borrower, TIER_LIMITS[1]);
+            assert_eq!(client.call(&ink_e2e::alice(), &shortfall).dry_run().await?.return_value(), 0);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn verify_wiring_spots_a_dependency_on_another_config<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let protocol = deploy(&mut client).await;
+            let mut loan_manager_ref = LoanManagerRef::from_addr(protocol.loan_manager);
+            let wiring = loan_manager_ref.call_mut().verify_wiring();
+            assert!(client.call(&ink_e2e::alice(), &wiring).dry_run().await?.return_value());
+
+            // A loan manager reading a second Config while its dependencies read the first
+            let other_config = client
+                .instantiate("config", &ink_e2e::alice(), &mut ConfigRef::new(account_of(&ink_e2e::alice())))
+                .submit()
+                .await
+                .expect("config instantiate failed")
+                .addr;
+            let miswired = client
+                .instantiate(
+                    "loan_manager",
+                    &ink_e2e::alice(),
+                    &mut LoanManagerRef::new(other_config, protocol.reputation, protocol.lending_pool, protocol.vouch),
+                )
+                .submit()
+                .await
+                .expect("loan_manager instantiate failed")
+                .addr;
+            let mut miswired_ref = LoanManagerRef::from_addr(miswired);
+            let wiring = miswired_ref.call_mut().verify_wiring();
+            assert!(!client.call(&ink_e2e::alice(), &wiring).dry_run().await?.return_value());
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn dependent_config_mirrors_the_config_contract<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let protocol = deploy(&mut client).await;
+            let alice = ink_e2e::alice();
+            let mut config_ref = ConfigRef::from_addr(protocol.config);
+            let mut loan_manager_ref = LoanManagerRef::from_addr(protocol.loan_manager);
+            let mut vouch_ref = VouchRef::from_addr(protocol.vouch);
+            let mut reputation_ref = ReputationRef::from_addr(protocol.reputation);
+
+            let seen = client.call(&alice, &loan_manager_ref.call_mut().dependent_config()).dry_run().await?.return_value();
+            assert_eq!(seen.admin, account_of(&alice));
+            assert_eq!(seen.rate_scale, client.call(&alice, &config_ref.call_mut().get_rate_scale()).dry_run().await?.return_value());
+            assert_eq!(seen.loan_tier1_requirements, client.call(&alice, &config_ref.call_mut().loan_tier1_requirements()).dry_run().await?.return_value());
+            assert_eq!(seen.loan_tier3_max_amount, client.call(&alice, &config_ref.call_mut().loan_tier3_max_amount()).dry_run().await?.return_value());
+            assert_eq!(seen.appeal_window, client.call(&alice, &config_ref.call_mut().get_appeal_window()).dry_run().await?.return_value());
+
+            let seen = client.call(&alice, &vouch_ref.call_mut().dependent_config()).dry_run().await?.return_value();
+            assert_eq!(seen.admin, account_of(&alice));
+            assert_eq!(seen.exposure_cap, client.call(&alice, &config_ref.call_mut().get_exposure_cap()).dry_run().await?.return_value());
+            assert_eq!(seen.max_vouchers_per_borrower, client.call(&alice, &config_ref.call_mut().get_max_vouchers_per_borrower()).dry_run().await?.return_value());
+            assert_eq!(seen.slash_delay, client.call(&alice, &config_ref.call_mut().get_slash_delay()).dry_run().await?.return_value());
+
+            let seen = client.call(&alice, &reputation_ref.call_mut().dependent_config()).dry_run().await?.return_value();
+            assert_eq!(seen.admin, account_of(&alice));
+            assert_eq!(seen.initial_stars, client.call(&alice, &config_ref.call_mut().get_initial_stars()).dry_run().await?.return_value());
+            assert_eq!(seen.max_total_stars, client.call(&alice, &config_ref.call_mut().get_max_total_stars()).dry_run().await?.return_value());
+            assert_eq!(seen.require_registration, client.call(&alice, &config_ref.call_mut().get_require_registration()).dry_run().await?.return_value());
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn borrower_profile_reads_stars_from_reputation<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let protocol = deploy(&mut client).await;
+            let borrower = account_of(&ink_e2e::bob());
+            set_stars(&mut client, &protocol, borrower, 7).await;
+
+            let mut loan_manager_ref = LoanManagerRef::from_addr(protocol.loan_manager);
+            let profile = client
+                .call(&ink_e2e::alice(), &loan_manager_ref.call_mut().get_borrower_profile(borrower))
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(
+                profile,
+                BorrowerProfile {
+                    stars: 7,
+                    active_vouches: 0,
+                    vouched_capital: 0,
+                    active_loans: 0,
+                    outstanding_principal: 0,
+                }
+            );
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn vouch_for_many_skips_only_the_target_over_the_exposure_cap<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let protocol = deploy(&mut client).await;
+            let (alice, charlie) = (ink_e2e::alice(), ink_e2e::charlie());
+            let voucher = account_of(&charlie);
+            set_stars(&mut client, &protocol, voucher, 60).await;
+
+            // 1e12 on chain is a 10_000 deposit in 10 decimals
+            let mut pool_ref = LendingPoolRef::from_addr(protocol.lending_pool);
+            client.call(&charlie, &pool_ref.call_mut().deposit(voucher)).value(1_000_000_000_000).submit().await
+                .expect("deposit failed");
+            let mut config_ref = ConfigRef::from_addr(protocol.config);
+            client.call(&alice, &config_ref.call_mut().update_exposure_cap_absolute(1_500, account_of(&alice))).submit().await
+                .expect("update_exposure_cap_absolute failed");
+
+            let borrowers = [AccountId::from([0x60; 32]), AccountId::from([0x61; 32]), AccountId::from([0x62; 32])];
+            for borrower in borrowers {
+                pending_loan(&mut client, &protocol, borrower, 5, 1_000).await;
+            }
+
+            // 10% of the deposit stays under the 1_500 cap, 20% doesn't
+            let targets = vec![
+                (AccountIdMapper::to_address(borrowers[0].as_ref()), 1, 10),
+                (AccountIdMapper::to_address(borrowers[1].as_ref()), 1, 20),
+                (AccountIdMapper::to_address(borrowers[2].as_ref()), 1, 10),
+            ];
+            let mut vouch_ref = VouchRef::from_addr(protocol.vouch);
+            let results = client.call(&charlie, &vouch_ref.call_mut().vouch_for_many(targets)).submit().await
+                .expect("vouch_for_many failed")
+                .return_value();
+            assert_eq!(results, vec![Ok(()), Err(VouchError::ExposureCapExceeded), Ok(())]);
+
+            for (borrower, backed) in borrowers.into_iter().zip([true, false, true]) {
+                let vouching = client.call(&alice, &vouch_ref.call_mut().is_vouching(voucher, borrower)).dry_run().await?
+                    .return_value();
+                assert_eq!(vouching, backed);
+            }
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn a_voucher_banned_after_vouching_stops_counting<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let protocol = deploy(&mut client).await;
+            let (alice, charlie) = (ink_e2e::alice(), ink_e2e::charlie());
+            let voucher = account_of(&charlie);
+            set_stars(&mut client, &protocol, voucher, 60).await;
+            let mut pool_ref = LendingPoolRef::from_addr(protocol.lending_pool);
+            client.call(&charlie, &pool_ref.call_mut().deposit(voucher)).value(1_000_000_000_000).submit().await
+                .expect("deposit failed");
+
+            let borrower = AccountId::from([0x63; 32]);
+            let loan_id = pending_loan(&mut client, &protocol, borrower, 5, 1_000).await;
+            let mut vouch_ref = VouchRef::from_addr(protocol.vouch);
+            let targets = vec![(AccountIdMapper::to_address(borrower.as_ref()), 1, 10)];
+            let results = client.call(&charlie, &vouch_ref.call_mut().vouch_for_many(targets)).submit().await
+                .expect("vouch_for_many failed")
+                .return_value();
+            assert_eq!(results, vec![Ok(())]);
+
+            let vouches = client.call(&alice, &vouch_ref.call_mut().get_vouches_for(borrower)).dry_run().await?.return_value();
+            assert_eq!(vouches, 1);
+            let on_loan = client.call(&alice, &vouch_ref.call_mut().get_vouches_for_loan(loan_id)).dry_run().await?.return_value();
+            assert_eq!(on_loan, 1);
+
+            // Setting the voucher to zero stars bans them; their stake stays Active but no longer counts
+            let mut reputation_ref = ReputationRef::from_addr(protocol.reputation);
+            client.call(&alice, &reputation_ref.call_mut().set_stars(voucher, 0)).submit().await
+                .expect("set_stars failed");
+
+            let vouches = client.call(&alice, &vouch_ref.call_mut().get_vouches_for(borrower)).dry_run().await?.return_value();
+            assert_eq!(vouches, 0);
+            let on_loan = client.call(&alice, &vouch_ref.call_mut().get_vouches_for_loan(loan_id)).dry_run().await?.return_value();
+            assert_eq!(on_loan, 0);
+            let listed = client.call(&alice, &vouch_ref.call_mut().get_all_vouchers(borrower)).dry_run().await?.return_value();
+            assert!(listed.is_empty());
+            let active = client.call(&alice, &vouch_ref.call_mut().is_vouching(voucher, borrower)).dry_run().await?.return_value();
+            assert!(active);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn register_grants_the_configured_initial_stars<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let protocol = deploy(&mut client).await;
+            let alice = ink_e2e::alice();
+            let mut config_ref = ConfigRef::from_addr(protocol.config);
+            let mut reputation_ref = ReputationRef::from_addr(protocol.reputation);
+
+            let (early, late) = (AccountId::from([0x70; 32]), AccountId::from([0x71; 32]));
+            client.call(&alice, &reputation_ref.call_mut().register(early)).submit().await
+                .expect("register failed");
+            client.call(&alice, &config_ref.call_mut().update_initial_stars(12, account_of(&alice))).submit().await
+                .expect("update_initial_stars failed");
+            client.call(&alice, &reputation_ref.call_mut().register(late)).submit().await
+                .expect("register failed");
+
+            // The default of 7, then the updated 12; existing records keep what they got
+            let stars = client.call(&alice, &reputation_ref.call_mut().get_stars(early)).dry_run().await?.return_value();
+            assert_eq!(stars, 7);
+            let stars = client.call(&alice, &reputation_ref.call_mut().get_stars(late)).dry_run().await?.return_value();
+            assert_eq!(stars, 12);
+
+            Ok(())
+        }
     }
 }
@@ -12,6 +12,7 @@ mod reputation {
     use ink::storage::Lazy;
     use config::ConfigRef;
     use ink::prelude::vec::Vec;
+    use ink::primitives::AccountIdMapper;
 
     /// Struct for User Reputation
     #[ink::storage_item(packed)]
@@ -23,6 +24,81 @@ mod reputation {
         vouch_history: Vec<VouchStat>,
         creation_time: Timestamp,
         banned: bool,
+        // Lifetime counters, kept separately so pruning the history doesn't lose summary stats
+        loans_repaid: u32,
+        loans_defaulted: u32,
+        vouches_succeeded: u32,
+        vouches_failed: u32,
+        last_activity: Timestamp, // Last time the user staked, earned stars or closed a loan
+        unbanned_at: Timestamp, // Time of the last unban (0 = never unbanned)
+        boost_stars: u32, // Part of `stars` earned from vouch boosts, subject to boost star decay
+        boost_decayed_at: Timestamp, // Time up to which boost star decay has been applied
+    }
+
+    impl UserReputation {
+        /// Fresh reputation record for a user created at `now`
+        fn new(stars: u32, now: Timestamp) -> Self {
+            Self {
+                stars,
+                stars_at_stake: 0,
+                loan_history: Vec::new(),
+                vouch_history: Vec::new(),
+                creation_time: now,
+                banned: false,
+                loans_repaid: 0,
+                loans_defaulted: 0,
+                vouches_succeeded: 0,
+                vouches_failed: 0,
+                last_activity: now,
+                unbanned_at: 0,
+                boost_stars: 0,
+                boost_decayed_at: now,
+            }
+        }
+    }
+
+    /// Config values that shape a star grant in `add_stars`
+    struct GrantPolicy {
+        max_stars_per_grant: u32,
+        cooldown_period: Timestamp,
+        rehabilitation_cooldown: (Timestamp, u32), // (probation after an unban, accrual percent during it)
+        max_total_stars: u32, // 0 = no ceiling
+    }
+
+    impl GrantPolicy {
+        /// Limit the blast radius of a buggy or compromised granting contract
+        fn check(&self, amount: u32) -> Result<(), Error> {
+            if amount > self.max_stars_per_grant {
+                return Err(Error::GrantTooLarge);
+            }
+            Ok(())
+        }
+
+        /// Credit `amount` stars granted at `now`
+        /// Nothing accrues while the account is inside its creation cooldown, users on probation
+        /// after an unban accrue at the reduced rate, and the total is clamped to the ceiling.
+        fn credit(&self, rep: &mut UserReputation, amount: u32, now: Timestamp) {
+            // Ignore star accrual while the account is still inside its cooldown window.
+            if now.saturating_sub(rep.creation_time) < self.cooldown_period {
+                return;
+            }
+
+            // Users on probation after an unban accrue stars at a reduced rate
+            let (rehab_cooldown, accrual_percent) = self.rehabilitation_cooldown;
+            let amount = if rep.unbanned_at > 0 && now.saturating_sub(rep.unbanned_at) < rehab_cooldown {
+                ((amount as u64).saturating_mul(accrual_percent as u64) / 100) as u32
+            } else {
+                amount
+            };
+
+            rep.stars = rep.stars.saturating_add(amount);
+            rep.last_activity = now;
+
+            // Clamp to the configured ceiling on total stars (0 = no ceiling)
+            if self.max_total_stars > 0 && rep.stars > self.max_total_stars {
+                rep.stars = self.max_total_stars;
+            }
+        }
     }
 
     /// Struct for Loan State
@@ -39,7 +115,35 @@ mod reputation {
     pub struct VouchStat {
         borrower: AccountId,
         successful: bool,
+        resolved_at: Timestamp,
     }
+    /// Role a contract is authorized for in `authorized`
+    #[ink::storage_item(packed)]
+    #[derive(Debug, PartialEq, Eq, Copy, Clone)]
+    pub enum CallerRole {
+        LoanManager,
+        Vouch,
+    }
+
+    /// Config parameters the Reputation contract reads, as returned by `dependent_config`
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct DependentConfig {
+        pub admin: AccountId,
+        pub initial_stars: u32,
+        pub min_stars_to_vouch: u32,
+        pub cooldown_period: Timestamp,
+        pub max_stars_per_grant: u32,
+        pub max_total_stars: u32,
+        pub max_history_len: u32,
+        pub boost: u64,
+        pub boost_decay: (u32, u32),
+        pub boost_star_decay: (Timestamp, u32),
+        pub default_penalty: (Timestamp, u32),
+        pub rehabilitation_cooldown: (Timestamp, u32),
+        pub require_registration: bool,
+    }
+
     /// All information that is needed to store in the contract
     #[ink(storage)]
     pub struct Reputation {
@@ -48,9 +152,21 @@ mod reputation {
         user_reps: Mapping<AccountId, UserReputation>,
         vouch_contract: Lazy<Option<Address>>, // Authorized vouch contract address
         loan_manager: Lazy<Option<Address>>, // Authorized loan manager contract address
+        authorized: Mapping<Address, CallerRole>, // Contracts allowed to call gated messages, by role
+        total_stars: Lazy<u64>, // Stars held across all users, free plus staked
+        total_staked_stars: Lazy<u64>, // Stars locked in vouches across all users
     }
 
 
+    /// Events for reputation actions
+    #[ink(event)]
+    pub struct StarsAdjusted {
+        user: AccountId,
+        old_stars: u32,
+        new_stars: u32,
+        banned: bool,
+    }
+
     // Custom error types for the contract
     #[derive(Debug, PartialEq, Eq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
@@ -60,6 +176,8 @@ mod reputation {
         InsufficientStakedStars,
         UserBanned,
         Unauthorized,
+        GrantTooLarge,
+        AlreadyRegistered,
     }
 
     impl Reputation {
@@ -73,15 +191,19 @@ mod reputation {
                 user_reps: Mapping::default(),
                 vouch_contract: Lazy::default(),
                 loan_manager: Lazy::default(),
+                authorized: Mapping::default(),
+                total_stars: Lazy::default(),
+                total_staked_stars: Lazy::default(),
             }
         }
 
         /// Internal helper to check if caller is admin
+        /// Compares the actual transaction caller, so the admin can't be impersonated
         fn ensure_admin(&self) -> Result<(), Error> {
             let caller = Self::env().caller();
-            // if caller != self.admin {
-            //     return Err(Error::Unauthorized);
-            // }
+            if caller != AccountIdMapper::to_address(self.admin.as_ref()) {
+                return Err(Error::Unauthorized);
+            }
             Ok(())
         }
 
@@ -94,6 +216,7 @@ mod reputation {
                 return Err(Error::Unauthorized);
             }
             self.vouch_contract.set(&Some(vouch_address));
+            self.authorized.insert(vouch_address, &CallerRole::Vouch);
             Ok(())
         }
 
@@ -106,13 +229,197 @@ mod reputation {
                 return Err(Error::Unauthorized);
             }
             self.loan_manager.set(&Some(loan_manager_address));
+            self.authorized.insert(loan_manager_address, &CallerRole::LoanManager);
+            Ok(())
+        }
+
+        /// Authorize an additional contract to call messages gated to `role`
+        /// Only callable by the admin. Re-adding an address replaces its role.
+        #[ink(message)]
+        pub fn add_authorized(&mut self, addr: Address, role: CallerRole) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.authorized.insert(addr, &role);
+            Ok(())
+        }
+
+        /// Revoke a contract's authorization (e.g., a compromised or deprecated dependency)
+        /// Only callable by the admin. Also applies to the addresses registered at setup.
+        #[ink(message)]
+        pub fn remove_authorized(&mut self, addr: Address) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.authorized.remove(addr);
             Ok(())
         }
 
+        /// Check whether an address is currently authorized for any role
+        #[ink(message)]
+        pub fn is_authorized(&self, addr: Address) -> bool {
+            self.authorized.contains(addr)
+        }
+
+        /// Check whether a user has a reputation record, even one with zero stars
+        #[ink(message)]
+        pub fn is_registered(&self, user: AccountId) -> bool {
+            self.user_reps.contains(&user)
+        }
+
         /// Function to get stars of a user
         #[ink(message)]
         pub fn get_stars(&self, user: AccountId) -> u32 {
-            self.user_reps.get(&user).map_or(0, |rep| rep.stars)
+            self.load_reputation(user).map_or(0, |rep| rep.stars)
+        }
+
+        /// Get the last time a user was active (staked, earned stars or closed a loan)
+        /// Returns 0 for unknown users
+        #[ink(message)]
+        pub fn get_last_activity(&self, user: AccountId) -> Timestamp {
+            self.load_reputation(user).map_or(0, |rep| rep.last_activity)
+        }
+
+        /// Get a user's account metadata as (creation_time, banned)
+        /// Returns None for users without a reputation record
+        #[ink(message)]
+        pub fn get_account_meta(&self, user: AccountId) -> Option<(Timestamp, bool)> {
+            self.load_reputation(user).map(|rep| (rep.creation_time, rep.banned))
+        }
+
+        /// Register a new user with the configured initial stars
+        /// Starts the account's cooldown window; fails if the user already has a reputation record
+        #[ink(message)]
+        pub fn register(&mut self, user: AccountId) -> Result<(), Error> {
+            if self.user_reps.contains(&user) {
+                return Err(Error::AlreadyRegistered);
+            }
+            let now = Self::env().block_timestamp();
+            let rep = self.new_reputation(now);
+            self.store_reputation(user, &rep);
+            Ok(())
+        }
+
+        /// Get the total stars in circulation: free and staked stars across all users
+        /// Staking doesn't change it; slashing and lost stakes reduce it
+        #[ink(message)]
+        pub fn get_total_stars(&self) -> u64 {
+            self.total_stars.get_or_default()
+        }
+
+        /// Get the stars currently locked in vouches across all users
+        #[ink(message)]
+        pub fn get_total_staked_stars(&self) -> u64 {
+            self.total_staked_stars.get_or_default()
+        }
+
+        /// Internal: Persist a user's reputation, keeping total_stars and total_staked_stars
+        /// in step with the change
+        fn store_reputation(&mut self, user: AccountId, rep: &UserReputation) {
+            let held = |r: &UserReputation| (r.stars as u64).saturating_add(r.stars_at_stake as u64);
+            let old = self.user_reps.get(&user);
+            let before = old.as_ref().map_or(0, held);
+            let staked_before = old.as_ref().map_or(0, |r| r.stars_at_stake as u64);
+
+            let total = Self::apply_delta(self.total_stars.get_or_default(), before, held(rep));
+            self.total_stars.set(&total);
+            let total_staked = Self::apply_delta(
+                self.total_staked_stars.get_or_default(),
+                staked_before,
+                rep.stars_at_stake as u64,
+            );
+            self.total_staked_stars.set(&total_staked);
+            self.user_reps.insert(&user, rep);
+        }
+
+        /// Internal: Move an aggregate by the change from `before` to `after`
+        fn apply_delta(total: u64, before: u64, after: u64) -> u64 {
+            if after >= before {
+                total.saturating_add(after - before)
+            } else {
+                total.saturating_sub(before - after)
+            }
+        }
+
+        /// Internal: Read a user's stored reputation
+        /// Reads are raw: boost star decay is only applied on write paths, by load_for_update
+        fn load_reputation(&self, user: AccountId) -> Option<UserReputation> {
+            self.user_reps.get(&user)
+        }
+
+        /// Internal: Read a user's reputation for a write, with boost star decay applied up to now
+        /// The decay is persisted when the record is stored.
+        fn load_for_update(&self, user: AccountId) -> Option<UserReputation> {
+            let mut rep = self.load_reputation(user)?;
+            self.decay_boost(&mut rep, Self::env().block_timestamp());
+            Some(rep)
+        }
+
+        /// Internal: Apply boost star decay up to `now`, reading Config only when there is a boost
+        fn decay_boost(&self, rep: &mut UserReputation, now: Timestamp) {
+            if rep.boost_stars == 0 {
+                rep.boost_decayed_at = now;
+                return;
+            }
+            let (period, percent) = self.config.get_boost_star_decay();
+            Self::apply_boost_decay(rep, now, period, percent);
+        }
+
+        /// Internal: Remove the boost stars lost since the last decay step, `percent` per `period`
+        /// Only boost-derived stars decay; the result never goes below the user's base stars.
+        fn apply_boost_decay(rep: &mut UserReputation, now: Timestamp, period: Timestamp, percent: u32) {
+            if period == 0 || rep.boost_stars == 0 {
+                rep.boost_decayed_at = now;
+                return;
+            }
+            let periods = now.saturating_sub(rep.boost_decayed_at) / period;
+            if periods == 0 {
+                return;
+            }
+
+            // Stars lost to staking or slashing may already have eaten into the boost
+            let boost_stars = rep.boost_stars.min(rep.stars);
+
+            // remaining = boost_stars * ((100 - percent) / 100) ^ periods, by repeated squaring
+            const SCALE: u128 = 1_000_000_000;
+            let mut factor = SCALE;
+            let mut base = (100 - percent.min(100)) as u128 * SCALE / 100;
+            let mut exponent = periods;
+            while exponent > 0 && factor > 0 {
+                if exponent & 1 == 1 {
+                    factor = factor * base / SCALE;
+                }
+                base = base * base / SCALE;
+                exponent >>= 1;
+            }
+            let remaining = (boost_stars as u128 * factor / SCALE) as u32;
+
+            rep.stars -= boost_stars - remaining;
+            rep.boost_stars = remaining;
+            rep.boost_decayed_at = rep.boost_decayed_at.saturating_add(periods.saturating_mul(period));
+        }
+
+        /// Internal: Read a user's reputation, creating a fresh record for unknown users
+        /// unless Config's require_registration is set, in which case they get UserNotFound
+        fn load_or_create_reputation(&self, user: AccountId, now: Timestamp) -> Result<UserReputation, Error> {
+            match self.load_for_update(user) {
+                Some(rep) => Ok(rep),
+                None => Self::create_reputation(
+                    self.config.get_require_registration(),
+                    self.config.get_initial_stars(),
+                    now,
+                ),
+            }
+        }
+
+        /// Internal: Record for a user seen for the first time, refused with UserNotFound
+        /// when registration is required
+        fn create_reputation(require_registration: bool, initial_stars: u32, now: Timestamp) -> Result<UserReputation, Error> {
+            if require_registration {
+                return Err(Error::UserNotFound);
+            }
+            Ok(UserReputation::new(initial_stars, now))
+        }
+
+        /// Internal: Fresh reputation record with the configured initial stars
+        fn new_reputation(&self, now: Timestamp) -> UserReputation {
+            UserReputation::new(self.config.get_initial_stars(), now)
         }
 
         /// Function to add stars to a user
@@ -120,46 +427,57 @@ mod reputation {
         #[ink(message)]
         pub fn add_stars(&mut self, user: AccountId, amount: u32) -> Result<(), Error> {
             // Verify caller is an authorized contract (loan manager or vouch contract)
-            if !self.ensure_loan_manager().is_ok() || !self.ensure_vouch_contract().is_ok(){
+            if self.ensure_loan_manager().is_err() && self.ensure_vouch_contract().is_err() {
                 return Err(Error::Unauthorized);
             }
 
-            let now = Self::env().block_timestamp();
-            let cooldown_period = self.config.get_cooldown_period();
-
-            let mut rep = self.user_reps.get(&user).unwrap_or(UserReputation {
-                stars: 7, // user starts with 7 stars
-                stars_at_stake: 0,
-                loan_history: Vec::new(),
-                vouch_history: Vec::new(),
-                creation_time: now,
-                banned: false,
-            });
+            let policy = self.grant_policy();
+            policy.check(amount)?;
 
-            // Ignore star accrual while the account is still inside its cooldown window.
-            if now.saturating_sub(rep.creation_time) < cooldown_period {
-                self.user_reps.insert(&user, &rep);
-                return Ok(());
-            }
+            let now = Self::env().block_timestamp();
 
-            rep.stars += amount;
+            // user starts with the configured initial stars, unless registration is required
+            let mut rep = self.load_or_create_reputation(user, now)?;
+            policy.credit(&mut rep, amount, now);
 
-            self.user_reps.insert(&user, &rep);
+            self.store_reputation(user, &rep);
 
             Ok(())
         }
 
+        /// Internal: Read the Config values that shape a star grant
+        fn grant_policy(&self) -> GrantPolicy {
+            GrantPolicy {
+                max_stars_per_grant: self.config.get_max_stars_per_grant(),
+                cooldown_period: self.config.get_cooldown_period(),
+                rehabilitation_cooldown: self.config.get_rehabilitation_cooldown(),
+                max_total_stars: self.config.get_max_total_stars(),
+            }
+        }
+
         /// Function to check if a user can vouch based on their stars
         #[ink(message)]
         pub fn can_vouch(&self, user: AccountId) -> bool {
             let min_stars = self.config.get_min_stars_to_vouch();
-            let current_stars = self.user_reps.get(&user)
+            let current_stars = self.load_reputation(user)
                 .map(|rep| if rep.banned { 0 } else { rep.stars })
                 .unwrap_or(0);
 
             current_stars >= min_stars
         }
 
+        /// Function to check if a user is still in good standing as a voucher
+        /// Unlike `can_vouch`, stars already staked in vouches count towards the minimum,
+        /// so an existing voucher is not disqualified just by having staked.
+        #[ink(message)]
+        pub fn is_in_good_standing(&self, user: AccountId) -> bool {
+            let min_stars = self.config.get_min_stars_to_vouch();
+            match self.load_reputation(user) {
+                Some(rep) => !rep.banned && rep.stars.saturating_add(rep.stars_at_stake) >= min_stars,
+                None => false,
+            }
+        }
+
         /// Slash stars from a user (penalty for defaults)
         /// Only callable by the authorized loan manager contract
         #[ink(message)]
@@ -167,7 +485,7 @@ mod reputation {
             // Verify caller is the authorized loan manager
             self.ensure_loan_manager()?;
 
-            let mut rep = self.user_reps.get(&user).ok_or(Error::UserNotFound)?;
+            let mut rep = self.load_for_update(user).ok_or(Error::UserNotFound)?;
 
             // Saturating subtract - never go below 0
             rep.stars = rep.stars.saturating_sub(amount);
@@ -176,7 +494,7 @@ mod reputation {
                 rep.banned = true;
             }
 
-            self.user_reps.insert(&user, &rep);
+            self.store_reputation(user, &rep);
 
             Ok(())
         }
@@ -188,7 +506,7 @@ mod reputation {
             // Verify caller is the authorized vouch contract
             self.ensure_vouch_contract()?;
 
-            let mut rep = self.user_reps.get(&user).ok_or(Error::UserNotFound)?;
+            let mut rep = self.load_or_create_reputation(user, Self::env().block_timestamp())?;
 
             if rep.banned {
                 return Err(Error::UserBanned);
@@ -200,8 +518,9 @@ mod reputation {
 
             rep.stars -= amount;
             rep.stars_at_stake += amount;
+            rep.last_activity = Self::env().block_timestamp();
 
-            self.user_reps.insert(&user, &rep);
+            self.store_reputation(user, &rep);
 
             Ok(())
         }
@@ -213,7 +532,7 @@ mod reputation {
             // Verify caller is the authorized vouch contract
             self.ensure_vouch_contract()?;
 
-            let mut rep = self.user_reps.get(&user).ok_or(Error::UserNotFound)?;
+            let mut rep = self.load_for_update(user).ok_or(Error::UserNotFound)?;
 
             if rep.banned {
                 return Err(Error::UserBanned);
@@ -223,39 +542,146 @@ mod reputation {
                 return Err(Error::InsufficientStakedStars);
             }
 
+            let now = Self::env().block_timestamp();
+            let boost = if success { self.vouch_boost(&rep) } else { 0 };
+            let max_history_len = self.config.get_max_history_len();
+            Self::apply_vouch_outcome(&mut rep, amount, borrower, success, boost, now, max_history_len);
+
+            self.store_reputation(user, &rep);
+
+            Ok(())
+        }
+
+        /// Internal: Release `amount` staked stars after a vouch for `borrower` resolved
+        /// A successful vouch returns the stake plus `boost` bonus stars; a failed one forfeits it.
+        /// The outcome is counted and logged, keeping only the most recent `max_history_len` entries.
+        fn apply_vouch_outcome(rep: &mut UserReputation, amount: u32, borrower: AccountId, success: bool, boost: u32, now: Timestamp, max_history_len: u32) {
             // Remove the staked amount first
             rep.stars_at_stake -= amount;
+            rep.last_activity = now;
 
             if success {
                 // Successful vouch -> return stake + bonus (e.g., +2 stars)
-                rep.stars += amount + self.config.get_boost() as u32;
-
-                // Update vouch history with the actual borrower
-                rep.vouch_history.push(VouchStat {
-                    borrower,
-                    successful: true,
-                });
+                rep.stars += amount + boost;
+                rep.boost_stars = rep.boost_stars.saturating_add(boost);
+                rep.vouches_succeeded = rep.vouches_succeeded.saturating_add(1);
             } else {
                 // Failed vouch -> don't return stars as penalty
-                // Update vouch history with the actual borrower
-                rep.vouch_history.push(VouchStat {
-                    borrower,
-                    successful: false,
-                });
+                rep.vouches_failed = rep.vouches_failed.saturating_add(1);
             }
 
-            self.user_reps.insert(&user, &rep);
+            // Update vouch history with the actual borrower
+            rep.vouch_history.push(VouchStat {
+                borrower,
+                successful: success,
+                resolved_at: now,
+            });
+
+            // Keep only the most recent entries
+            Self::trim_history(&mut rep.vouch_history, max_history_len);
+        }
+
+        /// Internal: Bonus stars for a successful vouch
+        /// The full boost applies to a voucher's first `full_boost_vouches` successes;
+        /// later ones earn `reduced_boost_percent` of it so easy vouches can't be farmed.
+        /// Each failed vouch within the penalty window further cuts the boost by `penalty_percent`.
+        fn vouch_boost(&self, rep: &UserReputation) -> u32 {
+            Self::boost_for(
+                rep,
+                Self::env().block_timestamp(),
+                self.config.get_boost() as u32,
+                self.config.get_boost_decay(),
+                self.config.get_default_penalty(),
+            )
+        }
+
+        /// Internal: `vouch_boost` for the given Config values
+        /// boost_decay is (full_boost_vouches, reduced_boost_percent) and default_penalty is
+        /// (penalty_window, penalty_percent)
+        fn boost_for(rep: &UserReputation, now: Timestamp, boost: u32, boost_decay: (u32, u32), default_penalty: (Timestamp, u32)) -> u32 {
+            let (full_boost_vouches, reduced_boost_percent) = boost_decay;
+            let boost = if rep.vouches_succeeded < full_boost_vouches {
+                boost
+            } else {
+                ((boost as u64).saturating_mul(reduced_boost_percent as u64) / 100) as u32
+            };
+
+            let (penalty_window, penalty_percent) = default_penalty;
+            let recent_failures = rep.vouch_history.iter()
+                .filter(|stat| !stat.successful && now.saturating_sub(stat.resolved_at) <= penalty_window)
+                .count() as u64;
+            let penalty = recent_failures.saturating_mul(penalty_percent as u64).min(100);
+            ((boost as u64).saturating_mul(100 - penalty) / 100) as u32
+        }
+
+        /// Record the outcome of a loan in the borrower's history
+        /// Only callable by the authorized loan manager contract
+        #[ink(message)]
+        pub fn record_loan(&mut self, user: AccountId, amount: Balance, repaid: bool) -> Result<(), Error> {
+            // Verify caller is the authorized loan manager
+            self.ensure_loan_manager()?;
+
+            let mut rep = self.load_for_update(user).ok_or(Error::UserNotFound)?;
+
+            let max_history_len = self.config.get_max_history_len();
+            Self::apply_loan_outcome(&mut rep, amount, repaid, Self::env().block_timestamp(), max_history_len);
+
+            self.store_reputation(user, &rep);
 
             Ok(())
         }
 
+        /// Internal: Count and log a loan outcome, keeping only the most recent `max_history_len` entries
+        fn apply_loan_outcome(rep: &mut UserReputation, amount: Balance, repaid: bool, now: Timestamp, max_history_len: u32) {
+            if repaid {
+                rep.loans_repaid = rep.loans_repaid.saturating_add(1);
+            } else {
+                rep.loans_defaulted = rep.loans_defaulted.saturating_add(1);
+            }
+            rep.loan_history.push(LoanStat { amount, repaid });
+            rep.last_activity = now;
+
+            // Keep only the most recent entries
+            Self::trim_history(&mut rep.loan_history, max_history_len);
+        }
+
+        /// Get lifetime history counters for a user
+        /// Returns (loans_repaid, loans_defaulted, vouches_succeeded, vouches_failed)
+        #[ink(message)]
+        pub fn get_history_counts(&self, user: AccountId) -> (u32, u32, u32, u32) {
+            self.load_reputation(user).map_or((0, 0, 0, 0), |rep| {
+                (rep.loans_repaid, rep.loans_defaulted, rep.vouches_succeeded, rep.vouches_failed)
+            })
+        }
+
+        /// Get a voucher's success rate: successful / total resolved vouches, scaled by 1e4
+        /// (10_000 = every vouch succeeded). Uses the lifetime counters, so trimmed history still counts.
+        /// Returns 0 for users with no resolved vouches
+        #[ink(message)]
+        pub fn get_vouch_success_rate(&self, user: AccountId) -> u32 {
+            let Some(rep) = self.load_reputation(user) else {
+                return 0;
+            };
+            let total = (rep.vouches_succeeded as u64).saturating_add(rep.vouches_failed as u64);
+            if total == 0 {
+                return 0;
+            }
+            ((rep.vouches_succeeded as u64).saturating_mul(10_000) / total) as u32
+        }
+
+        /// Internal helper to drop the oldest entries so at most `max_len` remain
+        fn trim_history<T>(history: &mut Vec<T>, max_len: u32) {
+            let max_len = max_len as usize;
+            if history.len() > max_len {
+                let excess = history.len() - max_len;
+                history.drain(..excess);
+            }
+        }
+
         /// Internal helper to check if caller is the authorized vouch contract
         fn ensure_vouch_contract(&self) -> Result<(), Error> {
             let caller = Self::env().caller();
-            let vouch_contract = self.vouch_contract.get()
-                .and_then(|opt| opt)
-                .ok_or(Error::Unauthorized)?;
-            if caller != vouch_contract {
+            if self.authorized.get(caller) != Some(CallerRole::Vouch) {
                 return Err(Error::Unauthorized);
             }
             Ok(())
@@ -264,10 +690,7 @@ mod reputation {
         /// Internal helper to check if caller is the authorized loan manager
         fn ensure_loan_manager(&self) -> Result<(), Error> {
             let caller = Self::env().caller();
-            let loan_manager = self.loan_manager.get()
-                .and_then(|opt| opt)
-                .ok_or(Error::Unauthorized)?;
-            if caller != loan_manager {
+            if self.authorized.get(caller) != Some(CallerRole::LoanManager) {
                 return Err(Error::Unauthorized);
             }
             Ok(())
@@ -280,19 +703,43 @@ mod reputation {
             self.ensure_admin()?;
 
             let now = Self::env().block_timestamp();
-            let mut rep = self.user_reps.get(&user).unwrap_or(UserReputation {
-                stars: 7,
-                stars_at_stake: 0,
-                loan_history: Vec::new(),
-                vouch_history: Vec::new(),
-                creation_time: now,
-                banned: false,
-            });
+            let mut rep = self.load_for_update(user).unwrap_or_else(|| self.new_reputation(now));
 
             rep.stars = stars;
             rep.banned = false; // Unban if setting stars > 0
 
-            self.user_reps.insert(&user, &rep);
+            self.store_reputation(user, &rep);
+
+            Ok(())
+        }
+
+        /// Admin function: Set a user's absolute star count (dispute resolution)
+        /// Unlike `admin_set_stars`, which always unbans and keeps any boost, the banned flag
+        /// follows the new count (banned at zero, unbanned above zero), the whole count becomes
+        /// base stars so none of it decays as boost, and a StarsAdjusted event is emitted
+        /// Only callable by admin
+        #[ink(message)]
+        pub fn set_stars(&mut self, user: AccountId, stars: u32) -> Result<(), Error> {
+            self.ensure_admin()?;
+
+            let now = Self::env().block_timestamp();
+            // The boost is cleared below, so there is no decay to apply first
+            let mut rep = self.load_reputation(user).unwrap_or_else(|| self.new_reputation(now));
+
+            let old_stars = rep.stars;
+            rep.stars = stars;
+            rep.banned = stars == 0;
+            rep.boost_stars = 0;
+            rep.boost_decayed_at = now;
+
+            self.store_reputation(user, &rep);
+
+            self.env().emit_event(StarsAdjusted {
+                user,
+                old_stars,
+                new_stars: stars,
+                banned: rep.banned,
+            });
 
             Ok(())
         }
@@ -304,45 +751,463 @@ mod reputation {
             self.ensure_admin()?;
 
             let now = Self::env().block_timestamp();
-            let mut rep = self.user_reps.get(&user).unwrap_or(UserReputation {
-                stars: 7,
-                stars_at_stake: 0,
-                loan_history: Vec::new(),
-                vouch_history: Vec::new(),
-                creation_time: now,
-                banned: false,
-            });
+            let mut rep = self.load_for_update(user).unwrap_or_else(|| self.new_reputation(now));
 
             rep.stars = rep.stars.saturating_add(amount);
             rep.banned = false; // Unban if adding stars
 
-            self.user_reps.insert(&user, &rep);
+            self.store_reputation(user, &rep);
 
             Ok(())
         }
 
         /// Admin function: Unban a user (for testing)
+        /// Starts the rehabilitation cooldown, during which add_stars accrues at a reduced rate
         /// Only callable by admin
         #[ink(message)]
         pub fn admin_unban_user(&mut self, user: AccountId) -> Result<(), Error> {
             self.ensure_admin()?;
 
-            let mut rep = self.user_reps.get(&user).ok_or(Error::UserNotFound)?;
+            let mut rep = self.load_for_update(user).ok_or(Error::UserNotFound)?;
             
             if rep.stars == 0 {
                 rep.stars = 7; // Give at least 7 stars when unbanning
             }
             rep.banned = false;
+            rep.unbanned_at = Self::env().block_timestamp();
 
-            self.user_reps.insert(&user, &rep);
+            self.store_reputation(user, &rep);
 
             Ok(())
         }
 
+        /// Admin function: Trim a user's loan and vouch history to the configured maximum length
+        /// Lifetime counters are unaffected
+        /// Only callable by admin
+        #[ink(message)]
+        pub fn prune_history(&mut self, user: AccountId) -> Result<(), Error> {
+            self.ensure_admin()?;
+
+            let mut rep = self.load_for_update(user).ok_or(Error::UserNotFound)?;
+
+            let max_history_len = self.config.get_max_history_len();
+            Self::trim_history(&mut rep.loan_history, max_history_len);
+            Self::trim_history(&mut rep.vouch_history, max_history_len);
+
+            self.store_reputation(user, &rep);
+
+            Ok(())
+        }
+
+        /// Get the address of the Config contract this contract reads parameters from
+        #[ink(message)]
+        pub fn get_config_address(&self) -> Address {
+            ink::ToAddr::to_addr(&self.config)
+        }
+
+        /// Get every Config parameter this contract consumes, read through its ConfigRef
+        /// Lets integrators check the contract sees the expected values
+        #[ink(message)]
+        pub fn dependent_config(&self) -> DependentConfig {
+            DependentConfig {
+                admin: self.config.get_admin(),
+                initial_stars: self.config.get_initial_stars(),
+                min_stars_to_vouch: self.config.get_min_stars_to_vouch(),
+                cooldown_period: self.config.get_cooldown_period(),
+                max_stars_per_grant: self.config.get_max_stars_per_grant(),
+                max_total_stars: self.config.get_max_total_stars(),
+                max_history_len: self.config.get_max_history_len(),
+                boost: self.config.get_boost(),
+                boost_decay: self.config.get_boost_decay(),
+                boost_star_decay: self.config.get_boost_star_decay(),
+                default_penalty: self.config.get_default_penalty(),
+                rehabilitation_cooldown: self.config.get_rehabilitation_cooldown(),
+                require_registration: self.config.get_require_registration(),
+            }
+        }
+
         /// Admin function: Get admin address (for verification)
         #[ink(message)]
         pub fn get_admin(&self) -> AccountId {
             self.admin
         }
     }
-}
\ No newline at end of file
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const ADMIN: [u8; 32] = [0x01; 32];
+        const USER: [u8; 32] = [0x02; 32];
+
+        fn address_of(account: [u8; 32]) -> Address {
+            AccountIdMapper::to_address(&account)
+        }
+
+        fn new_reputation() -> Reputation {
+            ink::env::test::set_caller(address_of(ADMIN));
+            Reputation::new(Address::from([0x10; 20]), AccountId::from(ADMIN))
+        }
+
+        #[ink::test]
+        fn prune_history_rejects_non_admin() {
+            let mut reputation = new_reputation();
+            ink::env::test::set_caller(address_of(USER));
+            assert_eq!(reputation.prune_history(AccountId::from(USER)), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn set_stars_rejects_non_admin() {
+            let mut reputation = new_reputation();
+            ink::env::test::set_caller(address_of(USER));
+            assert_eq!(reputation.set_stars(AccountId::from(USER), 100), Err(Error::Unauthorized));
+            assert!(!reputation.is_registered(AccountId::from(USER)));
+        }
+
+        #[ink::test]
+        fn add_authorized_rejects_non_admin() {
+            let mut reputation = new_reputation();
+            ink::env::test::set_caller(address_of(USER));
+            assert_eq!(
+                reputation.add_authorized(address_of(USER), CallerRole::Vouch),
+                Err(Error::Unauthorized)
+            );
+            assert!(!reputation.is_authorized(address_of(USER)));
+        }
+
+        #[ink::test]
+        fn revoked_caller_is_rejected_while_other_still_works() {
+            let mut reputation = new_reputation();
+            let vouch_a = Address::from([0xA1; 20]);
+            let vouch_b = Address::from([0xB2; 20]);
+            assert_eq!(reputation.set_vouch_contract(vouch_a), Ok(()));
+            assert_eq!(reputation.add_authorized(vouch_b, CallerRole::Vouch), Ok(()));
+            assert_eq!(reputation.remove_authorized(vouch_a), Ok(()));
+            assert!(!reputation.is_authorized(vouch_a));
+            assert!(reputation.is_authorized(vouch_b));
+
+            // The revoked contract fails the role check; the remaining one gets past it
+            // and only fails on the unknown user
+            ink::env::test::set_caller(vouch_a);
+            assert_eq!(
+                reputation.unstake_stars(AccountId::from(USER), 1, AccountId::from(ADMIN), true),
+                Err(Error::Unauthorized)
+            );
+            ink::env::test::set_caller(vouch_b);
+            assert_eq!(
+                reputation.unstake_stars(AccountId::from(USER), 1, AccountId::from(ADMIN), true),
+                Err(Error::UserNotFound)
+            );
+        }
+
+        #[ink::test]
+        fn add_stars_rejects_callers_without_a_role() {
+            let mut reputation = new_reputation();
+            assert_eq!(reputation.set_loan_manager(Address::from([0xC3; 20])), Ok(()));
+            ink::env::test::set_caller(address_of(USER));
+            assert_eq!(reputation.add_stars(AccountId::from(USER), 1), Err(Error::Unauthorized));
+            assert!(!reputation.is_registered(AccountId::from(USER)));
+        }
+
+        #[ink::test]
+        fn total_stars_follow_stored_records() {
+            let mut reputation = new_reputation();
+            let user = AccountId::from(USER);
+            reputation.store_reputation(user, &UserReputation::new(10, 0));
+            assert_eq!(reputation.get_total_stars(), 10);
+
+            // Staking moves stars without changing the total; losing them reduces it
+            let mut rep = UserReputation::new(6, 0);
+            rep.stars_at_stake = 4;
+            reputation.store_reputation(user, &rep);
+            assert_eq!(reputation.get_total_stars(), 10);
+            rep.stars_at_stake = 1;
+            reputation.store_reputation(user, &rep);
+            assert_eq!(reputation.get_total_stars(), 7);
+        }
+
+        #[ink::test]
+        fn total_staked_stars_follow_stored_records() {
+            let mut reputation = new_reputation();
+            let mut rep = UserReputation::new(5, 0);
+            rep.stars_at_stake = 3;
+            reputation.store_reputation(AccountId::from(USER), &rep);
+            reputation.store_reputation(AccountId::from(ADMIN), &rep);
+            assert_eq!(reputation.get_total_staked_stars(), 6);
+
+            rep.stars_at_stake = 0;
+            reputation.store_reputation(AccountId::from(USER), &rep);
+            assert_eq!(reputation.get_total_staked_stars(), 3);
+        }
+
+        #[ink::test]
+        fn is_registered_reflects_stored_records() {
+            let mut reputation = new_reputation();
+            let user = AccountId::from(USER);
+            assert!(!reputation.is_registered(user));
+            assert_eq!(reputation.get_account_meta(user), None);
+            reputation.store_reputation(user, &UserReputation::new(0, 0));
+            // A zero-star record still counts as registered
+            assert!(reputation.is_registered(user));
+        }
+
+        #[ink::test]
+        fn boost_stars_decay_while_base_stars_stay() {
+            // 10 base stars plus 40 from boosts, decaying 50% per 1_000 ms
+            let mut rep = UserReputation::new(50, 0);
+            rep.boost_stars = 40;
+
+            Reputation::apply_boost_decay(&mut rep, 999, 1_000, 50);
+            assert_eq!((rep.stars, rep.boost_stars, rep.boost_decayed_at), (50, 40, 0));
+
+            Reputation::apply_boost_decay(&mut rep, 2_500, 1_000, 50);
+            assert_eq!((rep.stars, rep.boost_stars), (20, 10));
+            // The half period left over counts toward the next step
+            assert_eq!(rep.boost_decayed_at, 2_000);
+
+            // After enough periods the boost is gone and only the base stars remain
+            Reputation::apply_boost_decay(&mut rep, 100_000, 1_000, 50);
+            assert_eq!((rep.stars, rep.boost_stars), (10, 0));
+
+            // A zero period disables decay
+            let mut rep = UserReputation::new(50, 0);
+            rep.boost_stars = 40;
+            Reputation::apply_boost_decay(&mut rep, 100_000, 0, 50);
+            assert_eq!((rep.stars, rep.boost_stars), (50, 40));
+        }
+
+        #[ink::test]
+        fn reads_return_stored_stars_without_decaying() {
+            let mut reputation = new_reputation();
+            let user = AccountId::from(USER);
+            let mut rep = UserReputation::new(50, 0);
+            rep.boost_stars = 40;
+            reputation.store_reputation(user, &rep);
+
+            // No Config read on the read path: the stored count comes back as is
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_000_000);
+            assert_eq!(reputation.get_stars(user), 50);
+
+            // Without a boost there is nothing to decay, so Config isn't read either
+            let mut plain = UserReputation::new(10, 0);
+            reputation.decay_boost(&mut plain, 1_000_000);
+            assert_eq!((plain.stars, plain.boost_decayed_at), (10, 1_000_000));
+        }
+
+        #[ink::test]
+        fn set_stars_moves_the_count_and_flips_the_ban() {
+            let mut reputation = new_reputation();
+            let user = AccountId::from(USER);
+            let mut rep = UserReputation::new(20, 0);
+            rep.boost_stars = 8;
+            reputation.store_reputation(user, &rep);
+
+            // Up: the whole count is base stars now, so none of it decays as boost
+            assert_eq!(reputation.set_stars(user, 35), Ok(()));
+            assert_eq!(reputation.get_stars(user), 35);
+            assert_eq!(reputation.user_reps.get(&user).unwrap().boost_stars, 0);
+            assert_eq!(reputation.get_total_stars(), 35);
+
+            // Down to zero bans
+            assert_eq!(reputation.set_stars(user, 0), Ok(()));
+            assert_eq!(reputation.get_account_meta(user), Some((0, true)));
+
+            // Back above zero unbans
+            assert_eq!(reputation.set_stars(user, 3), Ok(()));
+            assert_eq!(reputation.get_stars(user), 3);
+            assert_eq!(reputation.get_account_meta(user), Some((0, false)));
+        }
+
+        #[ink::test]
+        fn trim_history_keeps_the_most_recent_entries() {
+            let mut history = vec![1, 2, 3, 4, 5];
+            Reputation::trim_history(&mut history, 3);
+            assert_eq!(history, vec![3, 4, 5]);
+            Reputation::trim_history(&mut history, 10);
+            assert_eq!(history, vec![3, 4, 5]);
+            Reputation::trim_history(&mut history, 0);
+            assert!(history.is_empty());
+        }
+
+        #[ink::test]
+        fn counters_stay_accurate_once_the_history_is_trimmed() {
+            let mut rep = UserReputation::new(10, 0);
+            for (index, repaid) in [true, true, false, true].into_iter().enumerate() {
+                Reputation::apply_loan_outcome(&mut rep, 100 * (index as Balance + 1), repaid, 50, 2);
+            }
+            // Only the last two loans are kept, but every outcome is counted
+            assert_eq!(rep.loan_history, vec![LoanStat { amount: 300, repaid: false }, LoanStat { amount: 400, repaid: true }]);
+            assert_eq!((rep.loans_repaid, rep.loans_defaulted, rep.last_activity), (3, 1, 50));
+
+            let borrower = AccountId::from([0x03; 32]);
+            rep.stars_at_stake = 6;
+            Reputation::apply_vouch_outcome(&mut rep, 2, borrower, false, 0, 60, 1);
+            Reputation::apply_vouch_outcome(&mut rep, 2, borrower, true, 1, 70, 1);
+            Reputation::apply_vouch_outcome(&mut rep, 2, borrower, true, 1, 80, 1);
+            assert_eq!(rep.vouch_history, vec![VouchStat { borrower, successful: true, resolved_at: 80 }]);
+            assert_eq!((rep.vouches_succeeded, rep.vouches_failed), (2, 1));
+            // The failed stake is forfeited, the others come back with their boost
+            assert_eq!((rep.stars, rep.stars_at_stake, rep.boost_stars), (16, 0, 2));
+        }
+
+        #[ink::test]
+        fn the_boost_drops_after_full_boost_vouches() {
+            // Full boost of 4 for the first 3 successes, 50% of it afterwards, no penalty
+            let mut rep = UserReputation::new(10, 0);
+            let borrower = AccountId::from([0x03; 32]);
+            let mut boosts = Vec::new();
+            for _ in 0..5 {
+                let boost = Reputation::boost_for(&rep, 0, 4, (3, 50), (0, 0));
+                boosts.push(boost);
+                rep.stars_at_stake = 1;
+                Reputation::apply_vouch_outcome(&mut rep, 1, borrower, true, boost, 0, 10);
+            }
+            assert_eq!(boosts, vec![4, 4, 4, 2, 2]);
+            assert_eq!(rep.boost_stars, 16);
+        }
+
+        #[ink::test]
+        fn a_recent_default_cuts_the_boost() {
+            let borrower = AccountId::from([0x03; 32]);
+            let clean = UserReputation::new(10, 0);
+
+            // A failed vouch 500 ms ago, inside a 1_000 ms window at 25% per failure
+            let mut penalized = UserReputation::new(10, 0);
+            penalized.stars_at_stake = 1;
+            Reputation::apply_vouch_outcome(&mut penalized, 1, borrower, false, 0, 1_500, 10);
+
+            assert_eq!(Reputation::boost_for(&clean, 2_000, 4, (10, 50), (1_000, 25)), 4);
+            assert_eq!(Reputation::boost_for(&penalized, 2_000, 4, (10, 50), (1_000, 25)), 3);
+            // Once the failure leaves the window the full boost is back
+            assert_eq!(Reputation::boost_for(&penalized, 2_501, 4, (10, 50), (1_000, 25)), 4);
+        }
+
+        /// Grants of up to 10 stars, a 1_000 ms creation cooldown, 25% accrual during a
+        /// 5_000 ms probation after an unban and no ceiling
+        fn grant_policy() -> GrantPolicy {
+            GrantPolicy {
+                max_stars_per_grant: 10,
+                cooldown_period: 1_000,
+                rehabilitation_cooldown: (5_000, 25),
+                max_total_stars: 0,
+            }
+        }
+
+        #[ink::test]
+        fn a_new_user_accrues_nothing_until_the_creation_cooldown_ends() {
+            let policy = grant_policy();
+            let mut rep = UserReputation::new(7, 2_000);
+            policy.credit(&mut rep, 8, 2_999);
+            assert_eq!((rep.stars, rep.last_activity), (7, 2_000));
+            policy.credit(&mut rep, 8, 3_000);
+            assert_eq!((rep.stars, rep.last_activity), (15, 3_000));
+        }
+
+        #[ink::test]
+        fn users_on_probation_accrue_at_the_reduced_rate() {
+            let policy = grant_policy();
+            let mut rep = UserReputation::new(0, 0);
+            rep.unbanned_at = 10_000;
+            // 25% of 8 while the probation runs
+            policy.credit(&mut rep, 8, 14_999);
+            assert_eq!(rep.stars, 2);
+        }
+
+        #[ink::test]
+        fn fully_rehabilitated_users_accrue_in_full() {
+            let policy = grant_policy();
+            let mut rep = UserReputation::new(0, 0);
+            rep.unbanned_at = 10_000;
+            policy.credit(&mut rep, 8, 15_000);
+            assert_eq!(rep.stars, 8);
+            // Never having been banned is the same as being fully rehabilitated
+            let mut never_banned = UserReputation::new(0, 0);
+            policy.credit(&mut never_banned, 8, 12_000);
+            assert_eq!(never_banned.stars, 8);
+        }
+
+        #[ink::test]
+        fn unknown_users_are_created_unless_registration_is_required() {
+            let created = Reputation::create_reputation(false, 7, 1_000).unwrap();
+            assert_eq!((created.stars, created.creation_time, created.banned), (7, 1_000, false));
+            assert_eq!(created.last_activity, 1_000);
+
+            assert!(matches!(Reputation::create_reputation(true, 7, 1_000), Err(Error::UserNotFound)));
+        }
+
+        #[ink::test]
+        fn oversized_grants_are_refused() {
+            let policy = grant_policy();
+            assert_eq!(policy.check(10), Ok(()));
+            assert_eq!(policy.check(11), Err(Error::GrantTooLarge));
+        }
+
+        #[ink::test]
+        fn grants_are_clamped_to_max_total_stars() {
+            let policy = GrantPolicy { max_total_stars: 100, ..grant_policy() };
+            let mut rep = UserReputation::new(95, 0);
+            policy.credit(&mut rep, 10, 2_000);
+            assert_eq!(rep.stars, 100);
+            // Already at the ceiling, a grant changes nothing
+            policy.credit(&mut rep, 1, 3_000);
+            assert_eq!(rep.stars, 100);
+            // Without a ceiling the same grant goes through in full
+            let mut uncapped = UserReputation::new(95, 0);
+            grant_policy().credit(&mut uncapped, 10, 2_000);
+            assert_eq!(uncapped.stars, 105);
+        }
+
+        #[ink::test]
+        fn new_records_start_at_the_configured_initial_stars() {
+            for initial_stars in [0, 7, 12] {
+                let rep = Reputation::create_reputation(false, initial_stars, 500).unwrap();
+                assert_eq!((rep.stars, rep.stars_at_stake, rep.boost_stars), (initial_stars, 0, 0));
+            }
+        }
+
+        #[ink::test]
+        fn vouch_success_rate_is_scaled_by_ten_thousand() {
+            let mut reputation = new_reputation();
+            let borrower = AccountId::from([0x03; 32]);
+            let record = |outcomes: &[bool]| {
+                let mut rep = UserReputation::new(10, 0);
+                for success in outcomes {
+                    rep.stars_at_stake = 1;
+                    Reputation::apply_vouch_outcome(&mut rep, 1, borrower, *success, 0, 0, 1);
+                }
+                rep
+            };
+            let (all_success, all_fail, mixed) = (AccountId::from([0x04; 32]), AccountId::from([0x05; 32]), AccountId::from([0x06; 32]));
+            reputation.store_reputation(all_success, &record(&[true, true, true]));
+            reputation.store_reputation(all_fail, &record(&[false, false]));
+            reputation.store_reputation(mixed, &record(&[true, false, true]));
+
+            assert_eq!(reputation.get_vouch_success_rate(all_success), 10_000);
+            assert_eq!(reputation.get_vouch_success_rate(all_fail), 0);
+            // 2 of 3, from the counters even though the history only keeps the last vouch
+            assert_eq!(reputation.get_vouch_success_rate(mixed), 6_666);
+            // No resolved vouches, or no record at all
+            reputation.store_reputation(AccountId::from(USER), &UserReputation::new(10, 0));
+            assert_eq!(reputation.get_vouch_success_rate(AccountId::from(USER)), 0);
+            assert_eq!(reputation.get_vouch_success_rate(AccountId::from([0x07; 32])), 0);
+        }
+
+        #[ink::test]
+        fn account_meta_reports_creation_time_and_the_ban_after_a_slash_to_zero() {
+            let mut reputation = new_reputation();
+            let user = AccountId::from(USER);
+            let loan_manager = Address::from([0xC3; 20]);
+            assert_eq!(reputation.set_loan_manager(loan_manager), Ok(()));
+            reputation.store_reputation(user, &UserReputation::new(5, 1_234));
+            assert_eq!(reputation.get_account_meta(user), Some((1_234, false)));
+
+            // No boost to decay, so the slash makes no Config read
+            ink::env::test::set_caller(loan_manager);
+            assert_eq!(reputation.slash_stars(user, 3), Ok(()));
+            assert_eq!(reputation.get_account_meta(user), Some((1_234, false)));
+            assert_eq!(reputation.slash_stars(user, 10), Ok(()));
+            assert_eq!(reputation.get_stars(user), 0);
+            assert_eq!(reputation.get_account_meta(user), Some((1_234, true)));
+        }
+    }
+}
@@ -4,6 +4,8 @@ use ink::env::{DefaultEnvironment, Environment};
 
 pub type AccountId = <DefaultEnvironment as Environment>::AccountId;
 
+pub use self::vouch::Error as VouchError;
+
 /// This contract manages vouch relationships between users
 
 #[ink::contract]
@@ -11,7 +13,8 @@ mod vouch {
     use ink::storage::Mapping;
     use ink::storage::Lazy;
     use ink::prelude::vec::Vec;
-    use config::ConfigRef;
+    use ink::primitives::AccountIdMapper;
+    use config::{mul_div, ConfigRef, Rounding};
     use reputation::ReputationRef;
     use lending_pool::LendingPoolRef;
 
@@ -21,7 +24,8 @@ mod vouch {
     pub enum Status {
         Active,
         Fulfilled,
-        Defaulted
+        Defaulted,
+        ResolutionFailed // Stake could not be fully settled; see failed_resolutions and retry_resolution
     }
 
     /// Struct for Vouch Relationship
@@ -32,7 +36,22 @@ mod vouch {
         staked_stars: u32,
         staked_capital: Balance,
         created_at: Timestamp,
-        status: Status
+        status: Status,
+        resolved_at: Timestamp, // Time the relationship was resolved (0 while Active)
+    }
+
+    /// Config parameters the Vouch contract reads, as returned by `dependent_config`
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct DependentConfig {
+        pub admin: AccountId,
+        pub exposure_cap: u64,
+        pub exposure_cap_absolute: Balance,
+        pub max_vouch_percent: u8,
+        pub max_vouchers_per_borrower: u32,
+        pub max_vouches_per_voucher: u32,
+        pub min_capital_per_star: Balance,
+        pub slash_delay: Timestamp,
     }
 
     /// All information that is needed to store in the contract
@@ -46,6 +65,9 @@ mod vouch {
         loan_vouchers: Mapping<u64, Vec<AccountId>>, // loan_id -> list of vouchers
         borrower_exposure: Mapping<AccountId, Balance>,
         borrower_vouchers: Mapping<AccountId, Vec<AccountId>>, // Kept for backward compatibility
+        voucher_backings: Mapping<AccountId, Vec<AccountId>>, // voucher -> borrowers they have vouched for
+        failed_resolutions: Mapping<(AccountId, AccountId), (u32, Balance, bool)>, // (voucher, borrower) -> (unsettled stars, unsettled capital, success)
+        migration_complete: Lazy<bool>, // Set by the first organic vouch; closes import_relationships
     }
 
     /// Events for the vouch contract
@@ -64,6 +86,25 @@ mod vouch {
         success: bool,
     }
 
+    /// Stake actually returned (success) or slashed (failure) when a vouch is resolved
+    #[ink(event)]
+    pub struct StakeReturned {
+        voucher: AccountId,
+        borrower: AccountId,
+        stars: u32,
+        capital: Balance,
+        slashed: bool,
+    }
+
+    /// Part of a vouch stake could not be settled because a cross-contract call failed
+    #[ink(event)]
+    pub struct ResolutionFailed {
+        voucher: AccountId,
+        borrower: AccountId,
+        pending_stars: u32,
+        pending_capital: Balance,
+    }
+
     /// Error types for the contract
     #[derive(Debug, PartialEq, Eq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]  
@@ -76,10 +117,20 @@ mod vouch {
         AlreadyResolved,
         RelationshipNotFound,
         Unauthorized,
+        TooManyVouchers,
+        UnbalancedStake,
+        PercentTooHigh,
+        TooManyActiveVouches,
+        MigrationComplete,
+        BatchTooLarge,
+        NoPendingLoan,
     }
 
 
     impl Vouch {
+        // Maximum number of targets accepted by vouch_for_many
+        const MAX_VOUCH_BATCH: usize = 10;
+
         #[ink(constructor)]
         pub fn new(config_address: Address, reputation_address: Address, lending_pool_address: Address) -> Self {
             let config =
@@ -96,7 +147,10 @@ mod vouch {
                 relationships: Mapping::default(),
                 loan_vouchers: Mapping::default(),
                 borrower_exposure: Mapping::default(),
-                borrower_vouchers: Mapping::default()
+                borrower_vouchers: Mapping::default(),
+                voucher_backings: Mapping::default(),
+                failed_resolutions: Mapping::default(),
+                migration_complete: Lazy::default(),
             }
         }
 
@@ -112,6 +166,28 @@ mod vouch {
             Ok(())
         }
 
+        /// Get the address of the Config contract this contract reads parameters from
+        #[ink(message)]
+        pub fn get_config_address(&self) -> Address {
+            ink::ToAddr::to_addr(&self.config)
+        }
+
+        /// Get every Config parameter this contract consumes, read through its ConfigRef
+        /// Lets integrators check the contract sees the expected values
+        #[ink(message)]
+        pub fn dependent_config(&self) -> DependentConfig {
+            DependentConfig {
+                admin: self.config.get_admin(),
+                exposure_cap: self.config.get_exposure_cap(),
+                exposure_cap_absolute: self.config.get_exposure_cap_absolute(),
+                max_vouch_percent: self.config.get_max_vouch_percent(),
+                max_vouchers_per_borrower: self.config.get_max_vouchers_per_borrower(),
+                max_vouches_per_voucher: self.config.get_max_vouches_per_voucher(),
+                min_capital_per_star: self.config.get_min_capital_per_star(),
+                slash_delay: self.config.get_slash_delay(),
+            }
+        }
+
         /// Vouch for a specific loan (called by loan_manager after validation)
         /// Only callable by loan_manager
         #[ink(message)]
@@ -126,6 +202,96 @@ mod vouch {
                 return Err(Error::Unauthorized);
             }
 
+            self.create_vouch(loan_id, borrower, voucher, stars, capital_percent)
+        }
+
+        /// Vouch for several borrowers in one call; the transaction caller is the voucher
+        /// Each target is (borrower, stars, capital_percent) and backs the borrower's latest pending loan.
+        /// Each target either applies in full or leaves no state behind, so a failing target
+        /// (e.g. exposure cap) doesn't abort the others. Returns one result per target, in order.
+        /// Loans are not disbursed here: the loan manager calls back into this contract while
+        /// disbursing, so ready loans are disbursed afterwards by anyone calling its try_disburse.
+        #[ink(message)]
+        pub fn vouch_for_many(&mut self, targets: Vec<(Address, u32, u8)>) -> Vec<Result<(), Error>> {
+            if targets.len() > Self::MAX_VOUCH_BATCH {
+                return targets.iter().map(|_| Err(Error::BatchTooLarge)).collect();
+            }
+            // Only the caller's own stars and capital can be staked
+            let voucher = Self::env().to_account_id(Self::env().caller());
+
+            targets
+                .into_iter()
+                .map(|(borrower, stars, capital_percent)| {
+                    let (loan_id, borrower) = self.pending_loan_of(borrower).ok_or(Error::NoPendingLoan)?;
+                    // Same per-loan bound the loan manager applies to single vouches
+                    let loan_vouchers = self.loan_vouchers.get(&loan_id).unwrap_or_default().len() as u32;
+                    if loan_vouchers >= self.config.get_max_vouchers_per_loan() {
+                        return Err(Error::TooManyVouchers);
+                    }
+                    self.create_vouch(loan_id, borrower, voucher, stars, capital_percent)
+                })
+                .collect()
+        }
+
+        /// Internal: Keep the reputation and financial commitments aligned: the staked capital
+        /// must cover `min_capital_per_star` for every staked star (0 = not enforced)
+        fn check_stake_balance(stars: u32, staked_capital: Balance, min_capital_per_star: Balance) -> Result<(), Error> {
+            let min_capital = (stars as Balance).saturating_mul(min_capital_per_star);
+            if staked_capital < min_capital {
+                return Err(Error::UnbalancedStake);
+            }
+            Ok(())
+        }
+
+        /// Internal: Per-borrower exposure cap relative to pool liquidity
+        /// exposure_cap is a fraction of total liquidity scaled by rate_scale
+        fn relative_exposure_cap(total_liquidity: Balance, exposure_cap: u64, rate_scale: u64) -> Balance {
+            (total_liquidity as u128)
+                .saturating_mul(exposure_cap as u128)
+                .checked_div(rate_scale as u128)
+                .unwrap_or(0) as Balance
+        }
+
+        /// Internal: Refuse a stake that would take the borrower's exposure above `max_allowed`
+        /// A zero cap refuses every stake
+        fn check_exposure(current_exposure: Balance, staked_capital: Balance, max_allowed: Balance) -> Result<(), Error> {
+            if max_allowed == 0 || current_exposure.saturating_add(staked_capital) > max_allowed {
+                return Err(Error::ExposureCapExceeded);
+            }
+            Ok(())
+        }
+
+        /// Internal: Ask the loan manager for a borrower's latest pending loan as (loan_id, borrower account)
+        fn pending_loan_of(&self, borrower: Address) -> Option<(u64, AccountId)> {
+            let loan_manager = self.loan_manager.get().and_then(|opt| opt)?;
+
+            use ink::env::call::{build_call, ExecutionInput, Selector};
+            use ink::env::DefaultEnvironment;
+
+            build_call::<DefaultEnvironment>()
+                .call(loan_manager)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("get_pending_loan")))
+                        .push_arg(&borrower)
+                )
+                .returns::<Option<(u64, AccountId)>>()
+                .try_invoke()
+                .ok()
+                .and_then(|result| result.ok())
+                .flatten()
+        }
+
+        /// Internal: Validate and record one vouch, shared by `vouch_for_loan` and `vouch_for_many`
+        /// Either every write happens or none does: the only write that can fail after another
+        /// succeeded (staking stars) is rolled back by releasing the capital lock taken just before
+        fn create_vouch(&mut self, loan_id: u64, borrower: AccountId, voucher: AccountId, stars: u32, capital_percent: u8) -> Result<(), Error> {
+            // A relationship awaiting retry_resolution must not be overwritten by a new vouch
+            if let Some(existing) = self.relationships.get(&(voucher, borrower)) {
+                if existing.status == Status::ResolutionFailed {
+                    return Err(Error::UnableToVouch);
+                }
+            }
+
             let voucher_stars = self.reputation.get_stars(voucher);
 
             // Check if voucher meets minimum stars requirement to vouch
@@ -136,6 +302,11 @@ mod vouch {
                 return Err(Error::NotEnoughStars);
             }
 
+            // Force diversification: no single vouch may stake more than max_vouch_percent of the deposit
+            if capital_percent > self.config.get_max_vouch_percent() {
+                return Err(Error::PercentTooHigh);
+            }
+
             let deposit = self.lending_pool.get_user_deposit(voucher);
             if deposit == 0 {
                 return Err(Error::NotEnoughCapital);
@@ -147,25 +318,55 @@ mod vouch {
                 return Err(Error::ZeroAmount);
             }
 
-            // Check exposure cap BEFORE staking (to avoid staking if cap is exceeded)
-            let exposure_cap = self.config.get_exposure_cap();
-            let current_exposure = self.borrower_exposure.get(&borrower).unwrap_or(0);
-            let total_liquidity = self.lending_pool.get_total_liquidity();
-            let max_allowed = (total_liquidity as u128)
-                .saturating_mul(exposure_cap as u128)
-                .checked_div(1_000_000_000u128)
-                .unwrap_or(0) as Balance;
+            Self::check_stake_balance(stars, staked_capital, self.config.get_min_capital_per_star())?;
 
-            if max_allowed == 0 || current_exposure.saturating_add(staked_capital) > max_allowed {
-                return Err(Error::ExposureCapExceeded);
+            // Cap the number of active vouchers per borrower so resolution stays bounded.
+            // Only an Active relationship is exempt: a resolved one left in borrower_vouchers
+            // no longer counts toward the cap, so it must not bypass it either
+            let borrower_vouchers_list = self.borrower_vouchers.get(&borrower).unwrap_or_default();
+            let already_backing = self.is_vouching(voucher, borrower);
+            if !already_backing
+                && self.count_active_vouchers(borrower) >= self.config.get_max_vouchers_per_borrower()
+            {
+                return Err(Error::TooManyVouchers);
             }
 
-            // Stake stars in Reputation (after all validations pass)
-            self.reputation.stake_stars(voucher, stars).map_err(|_| Error::UnableToVouch)?;
+            // Symmetrically, cap the borrowers a single voucher backs so their capital isn't spread thin
+            let backings = self.voucher_backings.get(&voucher).unwrap_or_default();
+            if !already_backing
+                && self.count_active_backings(voucher) >= self.config.get_max_vouches_per_voucher()
+            {
+                return Err(Error::TooManyActiveVouches);
+            }
+
+            // Check exposure cap BEFORE staking (to avoid staking if cap is exceeded).
+            // A nonzero absolute cap replaces the liquidity-relative one, so the cap
+            // doesn't shrink when the pool does.
+            let current_exposure = self.borrower_exposure.get(&borrower).unwrap_or(0);
+            let absolute_cap = self.config.get_exposure_cap_absolute();
+            let max_allowed = if absolute_cap > 0 {
+                absolute_cap
+            } else {
+                Self::relative_exposure_cap(
+                    self.lending_pool.get_total_liquidity(),
+                    self.config.get_exposure_cap(),
+                    self.config.get_rate_scale(),
+                )
+            };
+            Self::check_exposure(current_exposure, staked_capital, max_allowed)?;
 
             // Increase staked capital in LendingPool (prevents withdrawal of staked capital)
             self.lending_pool.increase_staked_capital(voucher, staked_capital).map_err(|_| Error::UnableToVouch)?;
 
+            // Stake stars in Reputation (after all validations pass); on failure undo the capital lock
+            if self.reputation.stake_stars(voucher, stars).is_err() {
+                let _ = self.lending_pool.decrease_staked_capital(voucher, staked_capital);
+                return Err(Error::UnableToVouch);
+            }
+
+            // Organic vouching has started, so imported state could now conflict with it
+            self.migration_complete.set(&true);
+
             // Store the relationship
             let key = (voucher, borrower);
             let relationship = VouchRelationship {
@@ -174,6 +375,7 @@ mod vouch {
                 staked_capital,
                 created_at: self.env().block_timestamp(),
                 status: Status::Active,
+                resolved_at: 0,
             };
             self.relationships.insert(&key, &relationship);
 
@@ -188,12 +390,19 @@ mod vouch {
             }
 
             // Track voucher in the borrower's voucher list (for backward compatibility)
-            let mut borrower_vouchers_list = self.borrower_vouchers.get(&borrower).unwrap_or_default();
+            let mut borrower_vouchers_list = borrower_vouchers_list;
             if !borrower_vouchers_list.contains(&voucher) {
                 borrower_vouchers_list.push(voucher);
                 self.borrower_vouchers.insert(&borrower, &borrower_vouchers_list);
             }
 
+            // Track the borrower in the voucher's backings
+            let mut backings = backings;
+            if !backings.contains(&borrower) {
+                backings.push(borrower);
+                self.voucher_backings.insert(&voucher, &backings);
+            }
+
             // Emit event
             self.env().emit_event(VouchCreated {
                 voucher,
@@ -206,10 +415,40 @@ mod vouch {
         }
 
         /// Get count of active vouches for a specific loan
+        /// Vouches from vouchers who are banned or below the minimum stars are not counted
         #[ink(message)]
         pub fn get_vouches_for_loan(&self, loan_id: u64) -> u32 {
             let vouchers = self.loan_vouchers.get(&loan_id).unwrap_or_default();
-            vouchers.len() as u32
+            vouchers
+                .into_iter()
+                .filter(|voucher| self.reputation.is_in_good_standing(*voucher))
+                .count() as u32
+        }
+
+        /// Get count of active vouches for a loan that were created at least `min_age` ago
+        /// Vouches from vouchers who are banned or below the minimum stars are not counted,
+        /// so they never satisfy a loan's disbursement requirement
+        #[ink(message)]
+        pub fn get_aged_vouches_for_loan(&self, loan_id: u64, borrower: AccountId, min_age: Timestamp) -> u32 {
+            self.aged_loan_vouchers(loan_id, borrower, min_age)
+                .into_iter()
+                .filter(|voucher| self.reputation.is_in_good_standing(*voucher))
+                .count() as u32
+        }
+
+        /// Internal: Vouchers whose Active vouch for this loan is at least `min_age` old
+        fn aged_loan_vouchers(&self, loan_id: u64, borrower: AccountId, min_age: Timestamp) -> Vec<AccountId> {
+            let now = self.env().block_timestamp();
+            self.loan_vouchers.get(&loan_id).unwrap_or_default()
+                .into_iter()
+                .filter(|voucher| {
+                    self.relationships.get(&(*voucher, borrower)).is_some_and(|rel| {
+                        rel.loan_id == loan_id
+                            && rel.status == Status::Active
+                            && now.saturating_sub(rel.created_at) >= min_age
+                    })
+                })
+                .collect()
         }
 
         /// Get all voucher addresses for a specific loan
@@ -235,14 +474,73 @@ mod vouch {
             total_staked as Balance
         }
 
+        /// Bulk-write vouch relationships from a previous deployment (one-time migration)
+        /// Only callable by the Config admin, and only before the first organic vouch.
+        /// Entries are (voucher, borrower, loan id, staked stars, staked capital in 10 decimals, status).
+        /// Normal checks are bypassed and no stake is moved: staked stars and capital must be
+        /// migrated in Reputation and LendingPool separately. Active relationships keep the loan
+        /// they back, so the loan manager resolves them like any other vouch when that loan closes.
+        #[ink(message)]
+        pub fn import_relationships(&mut self, entries: Vec<(AccountId, AccountId, u64, u32, Balance, Status)>) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.apply_import(entries)
+        }
+
+        /// Internal: Write imported relationships, unless organic vouching has already started
+        fn apply_import(&mut self, entries: Vec<(AccountId, AccountId, u64, u32, Balance, Status)>) -> Result<(), Error> {
+            if self.migration_complete.get_or_default() {
+                return Err(Error::MigrationComplete);
+            }
+
+            let now = self.env().block_timestamp();
+            for (voucher, borrower, loan_id, stars, capital, status) in entries {
+                let relationship = VouchRelationship {
+                    loan_id,
+                    staked_stars: stars,
+                    staked_capital: capital,
+                    created_at: now,
+                    status,
+                    resolved_at: if status == Status::Active { 0 } else { now },
+                };
+                self.relationships.insert(&(voucher, borrower), &relationship);
+
+                // Only Active stakes count towards exposure and the loan's voucher list
+                if status == Status::Active {
+                    let exposure = self.borrower_exposure.get(&borrower).unwrap_or(0);
+                    self.borrower_exposure.insert(&borrower, &exposure.saturating_add(capital));
+
+                    let mut loan_vouchers = self.loan_vouchers.get(&loan_id).unwrap_or_default();
+                    if !loan_vouchers.contains(&voucher) {
+                        loan_vouchers.push(voucher);
+                        self.loan_vouchers.insert(&loan_id, &loan_vouchers);
+                    }
+                }
+
+                let mut vouchers = self.borrower_vouchers.get(&borrower).unwrap_or_default();
+                if !vouchers.contains(&voucher) {
+                    vouchers.push(voucher);
+                    self.borrower_vouchers.insert(&borrower, &vouchers);
+                }
+
+                let mut backings = self.voucher_backings.get(&voucher).unwrap_or_default();
+                if !backings.contains(&borrower) {
+                    backings.push(borrower);
+                    self.voucher_backings.insert(&voucher, &backings);
+                }
+            }
+
+            Ok(())
+        }
+
         /// Get count of active vouches for a borrower (backward compatibility)
+        /// Vouches from vouchers who are banned or below the minimum stars are not counted
         #[ink(message)]
         pub fn get_vouches_for(&self, borrower: AccountId) -> u32 {
             let vouchers = self.borrower_vouchers.get(&borrower).unwrap_or_default();
             let mut count: u32 = 0;
             for voucher in vouchers {
                 if let Some(rel) = self.relationships.get(&(voucher, borrower)) {
-                    if rel.status == Status::Active {
+                    if rel.status == Status::Active && self.reputation.is_in_good_standing(voucher) {
                         count += 1;
                     }
                 }
@@ -251,16 +549,108 @@ mod vouch {
         }
 
         /// Get all voucher addresses for a borrower (backward compatibility)
+        /// Vouchers who are banned or below the minimum stars are skipped
         #[ink(message)]
         pub fn get_all_vouchers(&self, borrower: AccountId) -> Vec<AccountId> {
             self.borrower_vouchers.get(&borrower).unwrap_or_default()
+                .into_iter()
+                .filter(|voucher| self.reputation.is_in_good_standing(*voucher))
+                .collect()
+        }
+
+        /// Get aggregate vouch statistics for a borrower
+        /// Returns (active vouch count, total staked capital, total staked stars)
+        /// Only Active relationships are counted; staked capital is in 10 decimals (storage format)
+        #[ink(message)]
+        pub fn get_borrower_vouch_summary(&self, borrower: AccountId) -> (u32, Balance, u32) {
+            let vouchers = self.borrower_vouchers.get(&borrower).unwrap_or_default();
+            let mut count: u32 = 0;
+            let mut total_capital: Balance = 0;
+            let mut total_stars: u32 = 0;
+            for voucher in vouchers {
+                if let Some(rel) = self.relationships.get(&(voucher, borrower)) {
+                    if rel.status == Status::Active {
+                        count += 1;
+                        total_capital = total_capital.saturating_add(rel.staked_capital);
+                        total_stars = total_stars.saturating_add(rel.staked_stars);
+                    }
+                }
+            }
+            (count, total_capital, total_stars)
+        }
+
+        /// Check whether a voucher currently backs a borrower (Active relationship)
+        #[ink(message)]
+        pub fn is_vouching(&self, voucher: AccountId, borrower: AccountId) -> bool {
+            self.relationships.get(&(voucher, borrower))
+                .map_or(false, |rel| rel.status == Status::Active)
+        }
+
+        /// Get the vouch relationship between a voucher and a borrower, if any
+        /// `resolved_at` marks when it was resolved, for dispute windows (0 while Active)
+        #[ink(message)]
+        pub fn get_relationship(&self, voucher: AccountId, borrower: AccountId) -> Option<VouchRelationship> {
+            self.relationships.get(&(voucher, borrower))
+        }
+
+        /// Worst-case loss for a voucher if the borrower defaults
+        /// Returns (staked stars, staked capital in 10 decimals) of the Active relationship, or zeros
+        #[ink(message)]
+        pub fn potential_loss(&self, voucher: AccountId, borrower: AccountId) -> (u32, Balance) {
+            match self.relationships.get(&(voucher, borrower)) {
+                Some(rel) if rel.status == Status::Active => (rel.staked_stars, rel.staked_capital),
+                _ => (0, 0),
+            }
+        }
+
+        /// Number of Active relationships `resolve_all` would process for a borrower
+        /// Lets keepers gauge the cost of a resolution before attempting it
+        #[ink(message)]
+        pub fn resolvable_count(&self, borrower: AccountId) -> u32 {
+            self.count_active_vouchers(borrower)
+        }
+
+        /// Internal helper to check the transaction caller is the Config admin
+        fn ensure_admin(&self) -> Result<(), Error> {
+            let admin = self.config.get_admin();
+            if Self::env().caller() != AccountIdMapper::to_address(admin.as_ref()) {
+                return Err(Error::Unauthorized);
+            }
+            Ok(())
+        }
+
+        /// Internal helper to count active relationships for a borrower regardless of voucher standing
+        fn count_active_vouchers(&self, borrower: AccountId) -> u32 {
+            let vouchers = self.borrower_vouchers.get(&borrower).unwrap_or_default();
+            let mut count: u32 = 0;
+            for voucher in vouchers {
+                if let Some(rel) = self.relationships.get(&(voucher, borrower)) {
+                    if rel.status == Status::Active {
+                        count += 1;
+                    }
+                }
+            }
+            count
+        }
+
+        /// Internal: Count the borrowers a voucher currently backs (Active relationships)
+        fn count_active_backings(&self, voucher: AccountId) -> u32 {
+            let borrowers = self.voucher_backings.get(&voucher).unwrap_or_default();
+            let mut count: u32 = 0;
+            for borrower in borrowers {
+                if self.is_vouching(voucher, borrower) {
+                    count += 1;
+                }
+            }
+            count
         }
 
         /// Resolve all vouch relationships for a loan upon loan completion
         /// Only callable by the authorized loan manager contract
+        /// vouchers: The vouchers the loan manager recorded on the loan; only their relationships are settled.
         /// loan_amount: The loan amount (in 10 decimals). Only used when success=false to calculate recovery.
         #[ink(message)]
-        pub fn resolve_loan(&mut self, loan_id: u64, borrower: AccountId, success: bool, loan_amount: Balance, loan_manager_address: Address) -> Result<(), Error> {
+        pub fn resolve_loan(&mut self, loan_id: u64, borrower: AccountId, vouchers: Vec<AccountId>, success: bool, loan_amount: Balance, loan_manager_address: Address) -> Result<(), Error> {
             // Verify caller is the authorized loan manager
             let caller = Self::env().caller();
             let loan_manager = self.loan_manager.get()
@@ -271,19 +661,18 @@ mod vouch {
                 return Err(Error::Unauthorized);
             }
 
-            let vouchers = self.loan_vouchers.get(&loan_id).unwrap_or_default();
-
-            // Calculate total staked capital for this loan (before slashing)
-            let mut total_staked_for_loan = 0u128;
+            // Capital backing this loan, to split the loan amount across delayed slashes
+            let mut total_loan_capital = 0u128;
             for voucher in vouchers.iter() {
-                let key = (*voucher, borrower);
-                if let Some(rel) = self.relationships.get(&key) {
-                    if rel.loan_id == loan_id && rel.status == Status::Active {
-                        total_staked_for_loan += rel.staked_capital as u128;
+                if let Some(relationship) = self.relationships.get(&(*voucher, borrower)) {
+                    if relationship.loan_id == loan_id && relationship.status == Status::Active {
+                        total_loan_capital += relationship.staked_capital as u128;
                     }
                 }
             }
 
+            // Capital actually released or slashed (failed cross-contract calls are excluded)
+            let mut total_settled_capital = 0u128;
             for voucher in vouchers.iter() {
                 let key = (*voucher, borrower);
                 if let Some(mut relationship) = self.relationships.get(&key) {
@@ -292,68 +681,196 @@ mod vouch {
                         continue;
                     }
 
-                    // Update status
-                    relationship.status = if success { Status::Fulfilled } else { Status::Defaulted };
-                    self.relationships.insert(&key, &relationship);
-
-                    // Unstake/slash stars via Reputation
-                    self.reputation.unstake_stars(*voucher, relationship.staked_stars, borrower, success)
-                        .map_err(|_| Error::UnableToVouch)?;
-
-                    // If success, just decrease staked capital (release the lock, user keeps funds)
-                    // If failure, slash_stake will handle decreasing staked capital automatically
-                    if success {
-                        self.lending_pool.decrease_staked_capital(*voucher, relationship.staked_capital)
-                            .map_err(|_| Error::UnableToVouch)?;
-                    } else {
-                        // Slash capital via LendingPool (this also decreases staked capital tracking)
-                        self.lending_pool.slash_stake(*voucher, relationship.staked_capital)
-                            .map_err(|_| Error::UnableToVouch)?;
-                    }
-
-                    self.env().emit_event(VouchResolved {
-                        voucher: *voucher,
-                        borrower,
-                        success,
-                    });
+                    let loan_share = mul_div(loan_amount as u128, relationship.staked_capital as u128, total_loan_capital, Rounding::Down)
+                        .unwrap_or(0) as Balance;
+                    let (_stars, capital) = self.resolve_relationship(*voucher, borrower, &mut relationship, loan_share, success);
+                    total_settled_capital += capital as u128;
                 }
             }
 
             // Clear voucher list for this loan
             self.loan_vouchers.remove(&loan_id);
 
-            // Decrement borrower exposure by total staked capital for this loan
-            let mut current_exposure = self.borrower_exposure.get(&borrower).unwrap_or(0);
-            current_exposure = current_exposure.saturating_sub(total_staked_for_loan as Balance);
-            if current_exposure == 0 {
-                self.borrower_exposure.remove(&borrower);
-            } else {
-                self.borrower_exposure.insert(&borrower, &current_exposure);
+            // Decrement borrower exposure by the capital that was settled; capital of
+            // relationships whose resolution failed stays in the exposure until retried
+            self.reduce_exposure(borrower, total_settled_capital as Balance);
+
+            // If default (failure), handle recovery: compare slashed capital to loan amount.
+            // Delayed slashes are only scheduled here; the pool recovers them in commit_slash.
+            if !success && self.config.get_slash_delay() == 0 {
+                let total_slashed_10 = total_settled_capital as Balance;
+                let _ = self.lending_pool.handle_default_recovery(total_slashed_10, loan_amount);
+            }
+
+            Ok(())
+        }
+
+        /// Get the unsettled part of a vouch resolution, if a cross-contract call failed
+        /// Returns (unsettled stars, unsettled capital in 10 decimals, success)
+        #[ink(message)]
+        pub fn get_failed_resolution(&self, voucher: AccountId, borrower: AccountId) -> Option<(u32, Balance, bool)> {
+            self.failed_resolutions.get(&(voucher, borrower))
+        }
+
+        /// Retry settling a vouch whose resolution failed
+        /// Re-attempts only the unsettled stars/capital recorded in failed_resolutions.
+        /// Once everything is settled the relationship moves to Fulfilled or Defaulted.
+        /// Callable by anyone: the outcome was already decided by the original resolution.
+        #[ink(message)]
+        pub fn retry_resolution(&mut self, voucher: AccountId, borrower: AccountId) -> Result<(), Error> {
+            let key = (voucher, borrower);
+            let mut relationship = self.relationships.get(&key).ok_or(Error::RelationshipNotFound)?;
+            if relationship.status != Status::ResolutionFailed {
+                return Err(Error::AlreadyResolved);
             }
+            let (stars, capital, success) = self.failed_resolutions.get(&key)
+                .ok_or(Error::RelationshipNotFound)?;
 
-            // If default (failure), handle recovery: compare slashed capital to loan amount
-            if !success {
-                let total_staked_10 = total_staked_for_loan as Balance;
-                let _ = self.lending_pool.handle_default_recovery(total_staked_10, loan_amount);
+            let (_settled_stars, settled_capital) = self.settle_stake(voucher, borrower, stars, capital, capital, success);
+            self.reduce_exposure(borrower, settled_capital);
+
+            // Slashed capital recovered on retry goes back to the pool like any other recovery
+            if !success && settled_capital > 0 && self.config.get_slash_delay() == 0 {
+                let _ = self.lending_pool.handle_default_recovery(settled_capital, settled_capital);
+            }
+
+            if self.failed_resolutions.get(&key).is_none() {
+                relationship.status = if success { Status::Fulfilled } else { Status::Defaulted };
+                self.relationships.insert(&key, &relationship);
+                self.env().emit_event(VouchResolved {
+                    voucher,
+                    borrower,
+                    success,
+                });
             }
 
             Ok(())
         }
 
+        /// Internal: Resolve one active relationship and settle its stake
+        /// The relationship ends Fulfilled/Defaulted, or ResolutionFailed if part of the stake
+        /// could not be settled. loan_share is the part of the loan amount a delayed slash covers.
+        /// Returns the (stars, capital) that were actually settled.
+        fn resolve_relationship(&mut self, voucher: AccountId, borrower: AccountId, relationship: &mut VouchRelationship, loan_share: Balance, success: bool) -> (u32, Balance) {
+            // Update status and resolution time before the cross-contract calls
+            self.mark_resolved(voucher, borrower, relationship, success);
+
+            let settled = self.settle_stake(voucher, borrower, relationship.staked_stars, relationship.staked_capital, loan_share, success);
+            self.finish_resolution(voucher, borrower, relationship, success);
+
+            settled
+        }
+
+        /// Internal: Record a relationship's outcome and stamp it with the current block time
+        fn mark_resolved(&mut self, voucher: AccountId, borrower: AccountId, relationship: &mut VouchRelationship, success: bool) {
+            relationship.status = if success { Status::Fulfilled } else { Status::Defaulted };
+            relationship.resolved_at = self.env().block_timestamp();
+            self.relationships.insert(&(voucher, borrower), relationship);
+        }
+
+        /// Internal: Mark a relationship ResolutionFailed if settling its stake left anything
+        /// in failed_resolutions, otherwise emit VouchResolved
+        fn finish_resolution(&mut self, voucher: AccountId, borrower: AccountId, relationship: &mut VouchRelationship, success: bool) {
+            let key = (voucher, borrower);
+            if self.failed_resolutions.get(&key).is_some() {
+                relationship.status = Status::ResolutionFailed;
+                self.relationships.insert(&key, relationship);
+            } else {
+                self.env().emit_event(VouchResolved {
+                    voucher,
+                    borrower,
+                    success,
+                });
+            }
+        }
+
+        /// Internal: Return (success) or slash (failure) vouch stars and capital
+        /// A failing Reputation or LendingPool call doesn't abort the resolution; the unsettled
+        /// part is recorded in failed_resolutions and a ResolutionFailed event is emitted.
+        /// Returns the (stars, capital) that were actually settled.
+        fn settle_stake(&mut self, voucher: AccountId, borrower: AccountId, stars: u32, capital: Balance, loan_share: Balance, success: bool) -> (u32, Balance) {
+            // Unstake/slash stars via Reputation
+            let stars_settled = stars == 0 || self.reputation
+                .unstake_stars(voucher, stars, borrower, success)
+                .is_ok();
+
+            // If success, just decrease staked capital (release the lock, user keeps funds)
+            // If failure, slash_stake will handle decreasing staked capital automatically,
+            // or pre_slash schedules it for commit_slash when a slash delay is configured
+            let capital_settled = capital == 0 || if success {
+                self.lending_pool.decrease_staked_capital(voucher, capital).is_ok()
+            } else if self.config.get_slash_delay() > 0 {
+                self.lending_pool.pre_slash(voucher, capital, loan_share).is_ok()
+            } else {
+                self.lending_pool.slash_stake(voucher, capital).is_ok()
+            };
+
+            self.record_settlement(voucher, borrower, (stars, capital), (stars_settled, capital_settled), success)
+        }
+
+        /// Internal: Record the outcome of the Reputation and LendingPool calls of a settlement
+        /// Whatever wasn't settled goes to failed_resolutions with a ResolutionFailed event;
+        /// StakeReturned carries only what was. Returns the settled (stars, capital).
+        fn record_settlement(&mut self, voucher: AccountId, borrower: AccountId, stake: (u32, Balance), settled: (bool, bool), success: bool) -> (u32, Balance) {
+            let (stars, capital) = stake;
+            let (stars_settled, capital_settled) = settled;
+            let settled_stars = if stars_settled { stars } else { 0 };
+            let settled_capital = if capital_settled { capital } else { 0 };
+
+            let key = (voucher, borrower);
+            if stars_settled && capital_settled {
+                self.failed_resolutions.remove(&key);
+            } else {
+                let pending_stars = stars - settled_stars;
+                let pending_capital = capital - settled_capital;
+                self.failed_resolutions.insert(&key, &(pending_stars, pending_capital, success));
+                self.env().emit_event(ResolutionFailed {
+                    voucher,
+                    borrower,
+                    pending_stars,
+                    pending_capital,
+                });
+            }
+
+            self.env().emit_event(StakeReturned {
+                voucher,
+                borrower,
+                stars: settled_stars,
+                capital: settled_capital,
+                slashed: !success,
+            });
+
+            (settled_stars, settled_capital)
+        }
+
+        /// Internal: Decrease a borrower's exposure, removing the entry when it reaches zero
+        fn reduce_exposure(&mut self, borrower: AccountId, amount: Balance) {
+            let current_exposure = self.borrower_exposure.get(&borrower).unwrap_or(0)
+                .saturating_sub(amount);
+            if current_exposure == 0 {
+                self.borrower_exposure.remove(&borrower);
+            } else {
+                self.borrower_exposure.insert(&borrower, &current_exposure);
+            }
+        }
+
         /// Resolve all vouch relationships for a borrower (backward compatibility)
         /// Only callable by the authorized loan manager contract
         #[ink(message)]
         pub fn resolve_all(&mut self, borrower: AccountId, success: bool, loan_manager_address: Address) -> Result<(), Error> {
             // Verify caller is the authorized loan manager
+            let caller = Self::env().caller();
             let loan_manager = self.loan_manager.get()
                 .and_then(|opt| opt)
                 .ok_or(Error::Unauthorized)?;
-            if loan_manager_address != loan_manager {
+            // Verify both the caller and the parameter match the stored loan manager
+            if caller != loan_manager || loan_manager_address != loan_manager {
                 return Err(Error::Unauthorized);
             }
 
             let vouchers = self.borrower_vouchers.get(&borrower).unwrap_or_default();
 
+            let mut total_settled_capital = 0u128;
             for voucher in vouchers.iter() {
                 let key = (*voucher, borrower);
                 if let Some(mut relationship) = self.relationships.get(&key) {
@@ -361,35 +878,14 @@ mod vouch {
                         continue;
                     }
 
-                    // Update status
-                    relationship.status = if success { Status::Fulfilled } else { Status::Defaulted };
-                    self.relationships.insert(&key, &relationship);
-
-                    // Unstake/slash stars via Reputation
-                    self.reputation.unstake_stars(*voucher, relationship.staked_stars, borrower, success)
-                        .map_err(|_| Error::UnableToVouch)?;
-
-                    // If success, just decrease staked capital (release the lock, user keeps funds)
-                    // If failure, slash_stake will handle decreasing staked capital automatically
-                    if success {
-                        self.lending_pool.decrease_staked_capital(*voucher, relationship.staked_capital)
-                            .map_err(|_| Error::UnableToVouch)?;
-                    } else {
-                        // Slash capital via LendingPool (this also decreases staked capital tracking)
-                        self.lending_pool.slash_stake(*voucher, relationship.staked_capital)
-                            .map_err(|_| Error::UnableToVouch)?;
-                    }
-
-                    self.env().emit_event(VouchResolved {
-                        voucher: *voucher,
-                        borrower,
-                        success,
-                    });
+                    let loan_share = relationship.staked_capital;
+                    let (_stars, capital) = self.resolve_relationship(*voucher, borrower, &mut relationship, loan_share, success);
+                    total_settled_capital += capital as u128;
                 }
             }
 
-            // Reset borrower exposure to 0
-            self.borrower_exposure.remove(&borrower);
+            // Reduce borrower exposure by the settled capital; capital of failed resolutions remains
+            self.reduce_exposure(borrower, total_settled_capital as Balance);
 
             // Clear voucher list for this borrower
             self.borrower_vouchers.remove(&borrower);
@@ -398,4 +894,354 @@ mod vouch {
         }
     }
 
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const VOUCHER: [u8; 32] = [0x01; 32];
+        const BORROWER: [u8; 32] = [0x02; 32];
+
+        fn voucher() -> AccountId {
+            AccountId::from(VOUCHER)
+        }
+
+        fn borrower() -> AccountId {
+            AccountId::from(BORROWER)
+        }
+
+        fn new_vouch() -> Vouch {
+            Vouch::new(Address::from([0x10; 20]), Address::from([0x11; 20]), Address::from([0x12; 20]))
+        }
+
+        /// Record a relationship the way create_vouch and the resolution paths leave it
+        fn insert_relationship(vouch: &mut Vouch, voucher: AccountId, borrower: AccountId, loan_id: u64, status: Status) {
+            vouch.relationships.insert(&(voucher, borrower), &VouchRelationship {
+                loan_id,
+                staked_stars: 2,
+                staked_capital: 100,
+                created_at: 0,
+                status,
+                resolved_at: 0,
+            });
+            let mut vouchers = vouch.borrower_vouchers.get(&borrower).unwrap_or_default();
+            vouchers.push(voucher);
+            vouch.borrower_vouchers.insert(&borrower, &vouchers);
+            let mut backings = vouch.voucher_backings.get(&voucher).unwrap_or_default();
+            backings.push(borrower);
+            vouch.voucher_backings.insert(&voucher, &backings);
+            if status == Status::Active {
+                let mut loan_vouchers = vouch.loan_vouchers.get(&loan_id).unwrap_or_default();
+                loan_vouchers.push(voucher);
+                vouch.loan_vouchers.insert(&loan_id, &loan_vouchers);
+            }
+        }
+
+        #[ink::test]
+        fn resolved_relationships_do_not_count_toward_the_borrower_cap() {
+            let mut vouch = new_vouch();
+            insert_relationship(&mut vouch, voucher(), borrower(), 1, Status::Fulfilled);
+            insert_relationship(&mut vouch, AccountId::from([0x03; 32]), borrower(), 2, Status::Active);
+
+            assert_eq!(vouch.count_active_vouchers(borrower()), 1);
+            // A stale entry in borrower_vouchers is not an Active relationship, so it gets no cap exemption
+            assert!(vouch.borrower_vouchers.get(&borrower()).unwrap_or_default().contains(&voucher()));
+            assert!(!vouch.is_vouching(voucher(), borrower()));
+        }
+
+        #[ink::test]
+        fn vouch_summary_counts_only_active_relationships() {
+            let mut vouch = new_vouch();
+            insert_relationship(&mut vouch, voucher(), borrower(), 1, Status::Active);
+            insert_relationship(&mut vouch, AccountId::from([0x03; 32]), borrower(), 1, Status::Active);
+            insert_relationship(&mut vouch, AccountId::from([0x04; 32]), borrower(), 1, Status::Defaulted);
+            assert_eq!(vouch.get_borrower_vouch_summary(borrower()), (2, 200, 4));
+        }
+
+        #[ink::test]
+        fn resolve_loan_is_only_accepted_from_the_loan_manager() {
+            let mut vouch = new_vouch();
+            let loan_manager = Address::from([0xC3; 20]);
+            ink::env::test::set_caller(loan_manager);
+            // Not wired yet
+            assert_eq!(vouch.resolve_loan(1, borrower(), Vec::new(), true, 0, loan_manager), Err(Error::Unauthorized));
+
+            assert_eq!(vouch.set_loan_manager(loan_manager), Ok(()));
+            ink::env::test::set_caller(AccountIdMapper::to_address(&VOUCHER));
+            assert_eq!(vouch.resolve_loan(1, borrower(), Vec::new(), true, 0, loan_manager), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn resolve_loan_leaves_vouches_for_other_loans_alone() {
+            let mut vouch = new_vouch();
+            let loan_manager = Address::from([0xC3; 20]);
+            assert_eq!(vouch.set_loan_manager(loan_manager), Ok(()));
+            insert_relationship(&mut vouch, voucher(), borrower(), 2, Status::Active);
+
+            // The voucher is listed on loan 1, but their relationship backs loan 2
+            ink::env::test::set_caller(loan_manager);
+            assert_eq!(vouch.resolve_loan(1, borrower(), vec![voucher()], true, 0, loan_manager), Ok(()));
+            assert!(vouch.is_vouching(voucher(), borrower()));
+            assert_eq!(vouch.get_vouchers_for_loan(2), vec![voucher()]);
+        }
+
+        #[ink::test]
+        fn retry_resolution_only_applies_to_failed_resolutions() {
+            let mut vouch = new_vouch();
+            assert_eq!(vouch.retry_resolution(voucher(), borrower()), Err(Error::RelationshipNotFound));
+            insert_relationship(&mut vouch, voucher(), borrower(), 1, Status::Active);
+            assert_eq!(vouch.retry_resolution(voucher(), borrower()), Err(Error::AlreadyResolved));
+            assert_eq!(vouch.get_failed_resolution(voucher(), borrower()), None);
+        }
+
+        #[ink::test]
+        fn is_vouching_requires_an_active_relationship() {
+            let mut vouch = new_vouch();
+            assert!(!vouch.is_vouching(voucher(), borrower()));
+            insert_relationship(&mut vouch, voucher(), borrower(), 1, Status::Active);
+            assert!(vouch.is_vouching(voucher(), borrower()));
+            // The relationship is keyed by (voucher, borrower), not the other way round
+            assert!(!vouch.is_vouching(borrower(), voucher()));
+        }
+
+        #[ink::test]
+        fn potential_loss_is_the_active_stake() {
+            let mut vouch = new_vouch();
+            assert_eq!(vouch.potential_loss(voucher(), borrower()), (0, 0));
+            insert_relationship(&mut vouch, voucher(), borrower(), 1, Status::Active);
+            assert_eq!(vouch.potential_loss(voucher(), borrower()), (2, 100));
+            insert_relationship(&mut vouch, voucher(), AccountId::from([0x03; 32]), 2, Status::Fulfilled);
+            assert_eq!(vouch.potential_loss(voucher(), AccountId::from([0x03; 32])), (0, 0));
+        }
+
+        #[ink::test]
+        fn vouch_for_many_rejects_oversized_batches() {
+            let mut vouch = new_vouch();
+            ink::env::test::set_caller(AccountIdMapper::to_address(&VOUCHER));
+            let targets = vec![(Address::from([0x20; 20]), 1, 10); Vouch::MAX_VOUCH_BATCH + 1];
+            let results = vouch.vouch_for_many(targets);
+            assert_eq!(results.len(), Vouch::MAX_VOUCH_BATCH + 1);
+            assert!(results.iter().all(|result| *result == Err(Error::BatchTooLarge)));
+        }
+
+        #[ink::test]
+        fn vouch_for_many_reports_each_target_without_a_pending_loan() {
+            let mut vouch = new_vouch();
+            ink::env::test::set_caller(AccountIdMapper::to_address(&VOUCHER));
+            // No loan manager is wired, so no borrower has a pending loan
+            let targets = vec![(Address::from([0x20; 20]), 1, 10), (Address::from([0x21; 20]), 1, 10)];
+            let results = vouch.vouch_for_many(targets);
+            assert_eq!(results, vec![Err(Error::NoPendingLoan), Err(Error::NoPendingLoan)]);
+            assert!(!vouch.migration_complete.get_or_default());
+        }
+
+        #[ink::test]
+        fn active_backings_ignore_resolved_relationships() {
+            let mut vouch = new_vouch();
+            insert_relationship(&mut vouch, voucher(), borrower(), 1, Status::Active);
+            insert_relationship(&mut vouch, voucher(), AccountId::from([0x03; 32]), 2, Status::Defaulted);
+            insert_relationship(&mut vouch, voucher(), AccountId::from([0x04; 32]), 3, Status::ResolutionFailed);
+            assert_eq!(vouch.count_active_backings(voucher()), 1);
+        }
+
+        #[ink::test]
+        fn resolvable_count_matches_active_relationships() {
+            let mut vouch = new_vouch();
+            assert_eq!(vouch.resolvable_count(borrower()), 0);
+            insert_relationship(&mut vouch, voucher(), borrower(), 1, Status::Active);
+            insert_relationship(&mut vouch, AccountId::from([0x03; 32]), borrower(), 1, Status::ResolutionFailed);
+            assert_eq!(vouch.resolvable_count(borrower()), 1);
+        }
+
+        #[ink::test]
+        fn only_vouches_older_than_min_age_count_toward_disbursement() {
+            let mut vouch = new_vouch();
+            let fresh_voucher = AccountId::from([0x03; 32]);
+            insert_relationship(&mut vouch, voucher(), borrower(), 1, Status::Active);
+            insert_relationship(&mut vouch, fresh_voucher, borrower(), 1, Status::Active);
+            let mut fresh = vouch.relationships.get(&(fresh_voucher, borrower())).unwrap();
+            fresh.created_at = 900;
+            vouch.relationships.insert(&(fresh_voucher, borrower()), &fresh);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_000);
+
+            // Aged 1_000 vs 100 against a 500 minimum
+            assert_eq!(vouch.aged_loan_vouchers(1, borrower(), 500), vec![voucher()]);
+            // Both count once the fresh one reaches the minimum age too
+            assert_eq!(vouch.aged_loan_vouchers(1, borrower(), 100), vec![voucher(), fresh_voucher]);
+            // Without a minimum age every active vouch counts
+            assert_eq!(vouch.aged_loan_vouchers(1, borrower(), 0).len(), 2);
+            // Vouches for another loan never count
+            assert!(vouch.aged_loan_vouchers(2, borrower(), 0).is_empty());
+        }
+
+        #[ink::test]
+        fn resolve_all_is_only_accepted_from_the_loan_manager() {
+            let mut vouch = new_vouch();
+            let loan_manager = Address::from([0xC3; 20]);
+            assert_eq!(vouch.set_loan_manager(loan_manager), Ok(()));
+            insert_relationship(&mut vouch, voucher(), borrower(), 1, Status::Active);
+
+            // Passing the loan manager's address is not enough, the caller must be it
+            ink::env::test::set_caller(AccountIdMapper::to_address(&VOUCHER));
+            assert_eq!(vouch.resolve_all(borrower(), true, loan_manager), Err(Error::Unauthorized));
+            assert!(vouch.is_vouching(voucher(), borrower()));
+
+            // Nothing active to settle, so no cross-contract call is made
+            ink::env::test::set_caller(loan_manager);
+            assert_eq!(vouch.resolve_all(AccountId::from([0x03; 32]), true, loan_manager), Ok(()));
+        }
+
+        #[ink::test]
+        fn a_failing_slash_keeps_the_relationship_for_a_retry() {
+            let mut vouch = new_vouch();
+            insert_relationship(&mut vouch, voucher(), borrower(), 1, Status::Active);
+            vouch.borrower_exposure.insert(&borrower(), &100);
+            let mut relationship = vouch.relationships.get(&(voucher(), borrower())).unwrap();
+
+            // Stars were slashed but slash_stake failed, e.g. the capital was already withdrawn
+            let settled = vouch.record_settlement(voucher(), borrower(), (2, 100), (true, false), false);
+            assert_eq!(settled, (2, 0));
+            vouch.finish_resolution(voucher(), borrower(), &mut relationship, false);
+            vouch.reduce_exposure(borrower(), settled.1);
+
+            assert_eq!(vouch.get_relationship(voucher(), borrower()).unwrap().status, Status::ResolutionFailed);
+            assert_eq!(vouch.get_failed_resolution(voucher(), borrower()), Some((0, 100, false)));
+            // The unslashed capital is not written off the borrower's exposure
+            assert_eq!(vouch.borrower_exposure.get(&borrower()), Some(100));
+
+            // A retry that settles the rest clears the record
+            assert_eq!(vouch.record_settlement(voucher(), borrower(), (0, 100), (true, true), false), (0, 100));
+            assert_eq!(vouch.get_failed_resolution(voucher(), borrower()), None);
+        }
+
+        #[ink::test]
+        fn stake_returned_reports_only_what_was_settled() {
+            let mut vouch = new_vouch();
+
+            // Unstaking the stars failed, releasing the capital went through
+            assert_eq!(vouch.record_settlement(voucher(), borrower(), (2, 100), (false, true), true), (0, 100));
+            assert_eq!(vouch.get_failed_resolution(voucher(), borrower()), Some((2, 0, true)));
+
+            let events: Vec<_> = ink::env::test::recorded_events().into_iter().collect();
+            assert_eq!(events.len(), 2);
+            let failed = <ResolutionFailed as ink::scale::Decode>::decode(&mut &events[0].data[..]).unwrap();
+            assert_eq!((failed.voucher, failed.borrower), (voucher(), borrower()));
+            assert_eq!((failed.pending_stars, failed.pending_capital), (2, 0));
+            let returned = <StakeReturned as ink::scale::Decode>::decode(&mut &events[1].data[..]).unwrap();
+            assert_eq!((returned.stars, returned.capital, returned.slashed), (0, 100, false));
+        }
+
+        #[ink::test]
+        fn a_clean_settlement_records_no_failure() {
+            let mut vouch = new_vouch();
+            assert_eq!(vouch.record_settlement(voucher(), borrower(), (2, 100), (true, true), false), (2, 100));
+            assert_eq!(vouch.get_failed_resolution(voucher(), borrower()), None);
+
+            let events: Vec<_> = ink::env::test::recorded_events().into_iter().collect();
+            assert_eq!(events.len(), 1);
+            let returned = <StakeReturned as ink::scale::Decode>::decode(&mut &events[0].data[..]).unwrap();
+            assert_eq!((returned.stars, returned.capital, returned.slashed), (2, 100, true));
+        }
+
+        #[ink::test]
+        fn staked_capital_must_cover_every_staked_star() {
+            // 3 stars at 100 per star need at least 300 of capital
+            assert_eq!(Vouch::check_stake_balance(3, 299, 100), Err(Error::UnbalancedStake));
+            assert_eq!(Vouch::check_stake_balance(3, 300, 100), Ok(()));
+            assert_eq!(Vouch::check_stake_balance(3, 1_000, 100), Ok(()));
+            // Staking no stars needs no capital backing
+            assert_eq!(Vouch::check_stake_balance(0, 1, 100), Ok(()));
+            // The default of 0 leaves the ratio unenforced
+            assert_eq!(Vouch::check_stake_balance(50, 1, 0), Ok(()));
+        }
+
+        #[ink::test]
+        fn the_relative_exposure_cap_follows_pool_liquidity() {
+            // 5% (5e7 on a 1e9 scale) of 1_000_000
+            assert_eq!(Vouch::relative_exposure_cap(1_000_000, 50_000_000, 1_000_000_000), 50_000);
+            // A pool that shrinks shrinks the cap with it
+            assert_eq!(Vouch::relative_exposure_cap(100_000, 50_000_000, 1_000_000_000), 5_000);
+            // A zero scale yields no cap rather than a division by zero
+            assert_eq!(Vouch::relative_exposure_cap(1_000_000, 50_000_000, 0), 0);
+
+            // The relative cap binds: 40_000 already staked leaves room for 10_000 more
+            let relative = Vouch::relative_exposure_cap(1_000_000, 50_000_000, 1_000_000_000);
+            assert_eq!(Vouch::check_exposure(40_000, 10_000, relative), Ok(()));
+            assert_eq!(Vouch::check_exposure(40_000, 10_001, relative), Err(Error::ExposureCapExceeded));
+            // An empty pool refuses every stake
+            assert_eq!(Vouch::check_exposure(0, 1, Vouch::relative_exposure_cap(0, 50_000_000, 1_000_000_000)), Err(Error::ExposureCapExceeded));
+        }
+
+        #[ink::test]
+        fn the_absolute_exposure_cap_binds_on_its_own() {
+            // An absolute cap of 20_000 holds whatever the pool's liquidity
+            assert_eq!(Vouch::check_exposure(15_000, 5_000, 20_000), Ok(()));
+            assert_eq!(Vouch::check_exposure(15_000, 5_001, 20_000), Err(Error::ExposureCapExceeded));
+            assert_eq!(Vouch::check_exposure(0, 20_001, 20_000), Err(Error::ExposureCapExceeded));
+        }
+
+        #[ink::test]
+        fn imported_relationships_read_back_like_organic_ones() {
+            let mut vouch = new_vouch();
+            let other_voucher = AccountId::from([0x03; 32]);
+            let other_borrower = AccountId::from([0x04; 32]);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5_000);
+
+            assert_eq!(vouch.apply_import(vec![
+                (voucher(), borrower(), 7, 2, 100, Status::Active),
+                (other_voucher, borrower(), 7, 3, 250, Status::Active),
+                (voucher(), other_borrower, 8, 1, 40, Status::Fulfilled),
+            ]), Ok(()));
+
+            let imported = vouch.get_relationship(voucher(), borrower()).unwrap();
+            assert_eq!((imported.loan_id, imported.staked_stars, imported.staked_capital), (7, 2, 100));
+            assert_eq!((imported.created_at, imported.resolved_at), (5_000, 0));
+            assert!(vouch.is_vouching(voucher(), borrower()));
+            assert_eq!(vouch.potential_loss(other_voucher, borrower()), (3, 250));
+            assert_eq!(vouch.get_vouchers_for_loan(7), vec![voucher(), other_voucher]);
+            assert_eq!(vouch.resolvable_count(borrower()), 2);
+            assert_eq!(vouch.borrower_exposure.get(&borrower()), Some(350));
+            assert_eq!(vouch.count_active_backings(voucher()), 1);
+
+            // Resolved imports keep their outcome but carry no exposure and back no loan
+            let resolved = vouch.get_relationship(voucher(), other_borrower).unwrap();
+            assert_eq!((resolved.status, resolved.resolved_at), (Status::Fulfilled, 5_000));
+            assert!(!vouch.is_vouching(voucher(), other_borrower));
+            assert_eq!(vouch.borrower_exposure.get(&other_borrower), None);
+            assert!(vouch.get_vouchers_for_loan(8).is_empty());
+        }
+
+        #[ink::test]
+        fn import_is_refused_once_organic_vouching_started() {
+            let mut vouch = new_vouch();
+            assert_eq!(vouch.apply_import(vec![(voucher(), borrower(), 1, 2, 100, Status::Active)]), Ok(()));
+            // Importing is repeatable until the first organic vouch closes it
+            assert_eq!(vouch.apply_import(Vec::new()), Ok(()));
+
+            vouch.migration_complete.set(&true);
+            let late = AccountId::from([0x03; 32]);
+            assert_eq!(
+                vouch.apply_import(vec![(late, borrower(), 1, 2, 100, Status::Active)]),
+                Err(Error::MigrationComplete)
+            );
+            assert_eq!(vouch.get_relationship(late, borrower()), None);
+            assert_eq!(vouch.borrower_exposure.get(&borrower()), Some(100));
+        }
+
+        #[ink::test]
+        fn resolved_at_is_the_block_time_of_the_resolution() {
+            let mut vouch = new_vouch();
+            insert_relationship(&mut vouch, voucher(), borrower(), 1, Status::Active);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_000);
+            assert_eq!(vouch.get_relationship(voucher(), borrower()).unwrap().resolved_at, 0);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(4_200);
+            let mut relationship = vouch.relationships.get(&(voucher(), borrower())).unwrap();
+            vouch.mark_resolved(voucher(), borrower(), &mut relationship, false);
+
+            let resolved = vouch.get_relationship(voucher(), borrower()).unwrap();
+            assert_eq!((resolved.status, resolved.resolved_at), (Status::Defaulted, 4_200));
+            assert_eq!(resolved.created_at, 0);
+        }
+    }
+
 }
\ No newline at end of file